@@ -0,0 +1,390 @@
+use crate::diagnostic::{Diagnostic, Diagnostics};
+use crate::git;
+use anyhow::{bail, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub enum Outcome {
+    Changed,
+    Unchanged,
+    Failed(anyhow::Error),
+}
+
+#[derive(Debug, Default, clap::Args)]
+pub struct Options {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[arg(long)]
+    pub check: bool,
+}
+
+// Which files a command works on, for commands that only read them.
+#[derive(Debug, Default, clap::Args)]
+pub struct Selection {
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+
+    #[arg(long, value_name = "REV", conflicts_with = "staged")]
+    since: Option<String>,
+
+    #[arg(long)]
+    staged: bool,
+}
+
+impl Selection {
+    fn only_changed(&self) -> bool {
+        self.since.is_some() || self.staged
+    }
+
+    // Each file is looked up in the repository it belongs to, which need not be the one around the
+    // current directory.
+    fn changed(&self, paths: &[PathBuf]) -> Result<HashSet<PathBuf>> {
+        let mut dirs = HashSet::new();
+        let mut roots = HashSet::new();
+        for path in paths {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            if dirs.insert(dir) {
+                roots.insert(git::root(dir)?);
+            }
+        }
+
+        let mut changed = HashSet::new();
+        for root in roots {
+            changed.extend(match &self.since {
+                Some(rev) => git::changed_since(&root, rev)?,
+                None => git::staged(&root)?,
+            });
+        }
+        Ok(changed)
+    }
+
+    fn walk(&self, dir: &Path, defaults: &[&str]) -> Result<Vec<PathBuf>> {
+        let include = if self.include.is_empty() {
+            glob_set(defaults)?
+        } else {
+            glob_set(&self.include)?
+        };
+        let exclude = glob_set(&self.exclude)?;
+
+        let mut paths = Vec::new();
+        let walker = WalkBuilder::new(dir)
+            .hidden(false)
+            .add_custom_ignore_filename(".junkignore")
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                continue;
+            }
+
+            let path = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+            if include.is_match(path) && !exclude.is_match(path) {
+                paths.push(entry.into_path());
+            }
+        }
+        Ok(paths)
+    }
+}
+
+// Patterns match anywhere below the walked directory unless anchored with a leading slash, the
+// same way .gitignore does.
+pub fn glob_set<S: AsRef<str>>(patterns: &[S]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.as_ref();
+        let pattern = match pattern.strip_prefix('/') {
+            Some(pattern) => pattern.to_string(),
+            None => format!("**/{pattern}"),
+        };
+        builder.add(GlobBuilder::new(&pattern).literal_separator(true).build()?);
+    }
+    Ok(builder.build()?)
+}
+
+pub fn expand(
+    patterns: &[PathBuf],
+    selection: &Selection,
+    defaults: &[&str],
+) -> Result<Vec<PathBuf>> {
+    let here = [PathBuf::from(".")];
+    let patterns = match patterns {
+        [] if selection.only_changed() => &here,
+        patterns => patterns,
+    };
+
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        if pattern.is_dir() {
+            paths.extend(selection.walk(pattern, defaults)?);
+            continue;
+        }
+
+        let s = pattern.to_string_lossy();
+        if !s.contains(['*', '?', '[']) {
+            paths.push(pattern.clone());
+            continue;
+        }
+
+        let matches = glob::glob(&s)?.collect::<Result<Vec<_>, _>>()?;
+        if matches.is_empty() {
+            bail!("no files match {s}");
+        }
+        paths.extend(matches);
+    }
+
+    let mut seen = HashSet::new();
+    paths.retain(|p| seen.insert(p.clone()));
+    if selection.only_changed() {
+        let changed = selection.changed(&paths)?;
+        paths.retain(|p| fs::canonicalize(p).is_ok_and(|p| changed.contains(&p)));
+    }
+    Ok(paths)
+}
+
+pub fn process<F>(path: &Path, options: &Options, f: F) -> Outcome
+where
+    F: FnOnce(&Path, &[u8]) -> Result<Vec<u8>>,
+{
+    match rewrite(path, options, f) {
+        Ok(true) => Outcome::Changed,
+        Ok(false) => Outcome::Unchanged,
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+// With --staged the content about to be committed is checked, not whatever else is in the working
+// tree, and the file is only rewritten when the two agree.
+fn rewrite<F>(path: &Path, options: &Options, f: F) -> Result<bool>
+where
+    F: FnOnce(&Path, &[u8]) -> Result<Vec<u8>>,
+{
+    let staged = options.selection.staged;
+    let input = if staged {
+        git::staged_content(path)?
+    } else {
+        fs::read(path)?
+    };
+    let output = f(path, &input)?;
+    if output == input {
+        return Ok(false);
+    } else if options.check {
+        return Ok(true);
+    }
+
+    if staged && fs::read(path)? != input {
+        bail!("has unstaged changes, stage or stash them first");
+    }
+    fs::write(path, output)?;
+    Ok(true)
+}
+
+pub fn run<F>(patterns: &[PathBuf], options: &Options, defaults: &[&str], f: F) -> Result<()>
+where
+    F: Fn(&Path, &[u8]) -> Result<Vec<u8>> + Sync,
+{
+    let paths = expand(patterns, &options.selection, defaults)?;
+    let outcomes = process_all(&paths, options, f);
+    report(paths.into_iter().zip(outcomes), options.check)
+}
+
+pub fn process_all<F>(paths: &[PathBuf], options: &Options, f: F) -> Vec<Outcome>
+where
+    F: Fn(&Path, &[u8]) -> Result<Vec<u8>> + Sync,
+{
+    paths
+        .par_iter()
+        .map(|path| process(path, options, &f))
+        .collect()
+}
+
+pub fn report<I, P>(outcomes: I, check: bool) -> Result<()>
+where
+    I: IntoIterator<Item = (P, Outcome)>,
+    P: AsRef<Path>,
+{
+    let (mut changed, mut unchanged, mut failed) = (0, 0, 0);
+    for (path, outcome) in outcomes {
+        match outcome {
+            Outcome::Changed => {
+                changed += 1;
+                if check {
+                    eprintln!("error: {}: not sorted", path.as_ref().display());
+                }
+            }
+            Outcome::Unchanged => unchanged += 1,
+            Outcome::Failed(e) => {
+                failed += 1;
+                report_error(path.as_ref(), &e);
+            }
+        }
+    }
+
+    let verb = if check { "would change" } else { "changed" };
+    let summary = format!("{changed} {verb}, {unchanged} unchanged, {failed} failed");
+    if failed > 0 || (check && changed > 0) {
+        bail!(summary);
+    }
+
+    println!("{summary}");
+    Ok(())
+}
+
+pub fn report_warnings(path: &Path, warnings: &[String]) {
+    for warning in warnings {
+        eprintln!("warning: {}: {warning}", path.display());
+    }
+}
+
+// Diagnostics already name the file they are about.
+pub fn report_error(path: &Path, e: &anyhow::Error) {
+    if e.is::<Diagnostic>() || e.is::<Diagnostics>() {
+        eprintln!("error: {e}");
+    } else {
+        eprintln!("error: {}: {e:#}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let dir = std::env::temp_dir().join(format!("junk-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+
+        let pattern = dir.join("*.txt");
+        let paths = expand(
+            &[pattern.clone(), dir.join("a.txt"), pattern],
+            &Selection::default(),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(paths.len(), 3);
+
+        let outcomes = process_all(&paths, &Options::default(), |path, input| {
+            match path.file_name().unwrap().to_str().unwrap() {
+                "a.txt" => Ok(input.to_vec()),
+                "b.txt" => Ok(b"changed".to_vec()),
+                _ => bail!("oops"),
+            }
+        });
+        assert!(matches!(
+            outcomes.as_slice(),
+            [Outcome::Unchanged, Outcome::Changed, Outcome::Failed(_)]
+        ));
+        assert_eq!(fs::read(dir.join("b.txt")).unwrap(), b"changed");
+
+        let error = report(paths.into_iter().zip(outcomes), false).unwrap_err();
+        assert_eq!(error.to_string(), "1 changed, 1 unchanged, 1 failed");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_walk() {
+        let dir = std::env::temp_dir().join(format!("junk-walk-{}", std::process::id()));
+        for name in ["a", "b", "c", "d/e", ".vscode"] {
+            fs::create_dir_all(dir.join(name)).unwrap();
+            fs::write(dir.join(name).join("pom.xml"), "").unwrap();
+        }
+        fs::write(dir.join("c/other.xml"), "").unwrap();
+        fs::write(dir.join(".junkignore"), "b/\n").unwrap();
+
+        let selection = Selection {
+            include: Vec::new(),
+            exclude: vec!["d/**".to_string()],
+            ..Selection::default()
+        };
+        let paths = expand(std::slice::from_ref(&dir), &selection, &["pom.xml"]).unwrap();
+        let paths = paths
+            .iter()
+            .map(|p| p.strip_prefix(&dir).unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, [".vscode/pom.xml", "a/pom.xml", "c/pom.xml"]);
+
+        let selection = Selection {
+            include: vec!["/c/*.xml".to_string()],
+            exclude: Vec::new(),
+            ..Selection::default()
+        };
+        let paths = expand(std::slice::from_ref(&dir), &selection, &["pom.xml"]).unwrap();
+        assert_eq!(paths, [dir.join("c/other.xml"), dir.join("c/pom.xml")]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_staged_elsewhere() {
+        let dir = std::env::temp_dir().join(format!("junk-staged-{}", std::process::id()));
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::write(dir.join("a/pom.xml"), "").unwrap();
+        fs::write(dir.join("pom.xml"), "").unwrap();
+
+        let repo = git2::Repository::init(&dir).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a/pom.xml")).unwrap();
+        index.write().unwrap();
+
+        let selection = Selection {
+            staged: true,
+            ..Selection::default()
+        };
+        let paths = expand(std::slice::from_ref(&dir), &selection, &["pom.xml"]).unwrap();
+        assert_eq!(paths, [dir.join("a/pom.xml")]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_process_staged() {
+        let dir = std::env::temp_dir().join(format!("junk-index-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let repo = git2::Repository::init(&dir).unwrap();
+        let path = dir.join("a.txt");
+        fs::write(&path, "sorted").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(&path, "unsorted").unwrap();
+
+        let sort = |_: &Path, _: &[u8]| Ok(b"sorted".to_vec());
+        let mut options = Options {
+            selection: Selection {
+                staged: true,
+                ..Selection::default()
+            },
+            check: true,
+        };
+        assert!(matches!(process(&path, &options, sort), Outcome::Unchanged));
+
+        options.check = false;
+        let unsort = |_: &Path, _: &[u8]| Ok(b"unsorted".to_vec());
+        let Outcome::Failed(e) = process(&path, &options, unsort) else {
+            panic!("expected a failure");
+        };
+        assert_eq!(
+            e.to_string(),
+            "has unstaged changes, stage or stash them first"
+        );
+        assert_eq!(fs::read(&path).unwrap(), b"unsorted");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
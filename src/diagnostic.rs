@@ -0,0 +1,151 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub context: Option<String>,
+    pub message: String,
+    pub snippet: String,
+}
+
+impl Diagnostic {
+    pub fn new(path: &Path, text: &str, line: usize, column: usize, message: &str) -> Self {
+        let line = line.max(1);
+        Self {
+            path: path.to_path_buf(),
+            line,
+            column: column.max(1),
+            context: None,
+            message: message.to_string(),
+            snippet: text
+                .lines()
+                .nth(line - 1)
+                .unwrap_or_default()
+                .trim_end()
+                .to_string(),
+        }
+    }
+
+    pub fn at(path: &Path, text: &str, offset: usize, message: &str) -> Self {
+        let offset = offset.min(text.len());
+        let start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = text[..start].matches('\n').count() + 1;
+        let column = text[start..offset].chars().count() + 1;
+        Self::new(path, text, line, column, message)
+    }
+
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        let context = context.into();
+        self.context = (!context.is_empty()).then_some(context);
+        self
+    }
+
+    pub fn json(path: &Path, text: &str, error: &serde_json::Error) -> Self {
+        let suffix = format!(" at line {} column {}", error.line(), error.column());
+        let message = error.to_string();
+        let message = message.strip_suffix(&suffix).unwrap_or(&message);
+
+        Self::new(path, text, error.line(), error.column(), message)
+    }
+
+    // The message keeps the path that serde_yaml puts in front of it.
+    pub fn yaml(path: &Path, text: &str, error: &serde_yaml::Error) -> Self {
+        let (line, column) = error
+            .location()
+            .map_or((0, 0), |location| (location.line(), location.column()));
+        let suffix = format!(" at line {line} column {column}");
+        let message = error.to_string();
+        let message = message.strip_suffix(&suffix).unwrap_or(&message);
+
+        Self::new(path, text, line, column, message)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.message
+        )?;
+        if let Some(context) = &self.context {
+            write!(f, "\n  in {context}")?;
+        }
+
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        let indent = self
+            .snippet
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        write!(
+            f,
+            "\n{pad} |\n{number} | {}\n{pad} | {indent}^",
+            self.snippet
+        )
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[derive(Debug)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, diagnostic) in self.0.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            diagnostic.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostics {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_at() {
+        let diagnostic =
+            Diagnostic::at(Path::new("a.xml"), "<a>\n  <b>\n</a>\n", 8, "oops").context("a/b");
+        assert_eq!(
+            diagnostic.to_string(),
+            "a.xml:2:5: oops\n  in a/b\n  |\n2 |   <b>\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let text = "{\n    \"a\": [1, x]\n}\n";
+        let error = serde_json::from_str::<serde_json::Value>(text).unwrap_err();
+        assert_eq!(
+            Diagnostic::json(Path::new("a.json"), text, &error).to_string(),
+            "a.json:2:14: expected value\n  |\n2 |     \"a\": [1, x]\n  |              ^"
+        );
+    }
+
+    #[test]
+    fn test_yaml() {
+        let text = "a:\n  - b: [1]\n";
+        let error =
+            serde_yaml::from_str::<HashMap<String, Vec<HashMap<String, u32>>>>(text).unwrap_err();
+        assert_eq!(
+            Diagnostic::yaml(Path::new("a.yaml"), text, &error).to_string(),
+            "a.yaml:2:8: a[0].b: invalid type: sequence, expected u32\n  |\n2 |   - b: [1]\n  |        ^"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Result};
+use git2::{Delta, Diff, DiffOptions, ErrorCode, Repository};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn changed_since(dir: &Path, rev: &str) -> Result<HashSet<PathBuf>> {
+    let repo = Repository::discover(dir)?;
+    let head = repo.head()?.peel_to_commit()?;
+    let base = repo.revparse_single(rev)?.peel_to_commit()?;
+    let base = repo.find_commit(repo.merge_base(base.id(), head.id())?)?;
+
+    let mut options = DiffOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let diff = repo.diff_tree_to_workdir_with_index(Some(&base.tree()?), Some(&mut options))?;
+    paths(&repo, &diff)
+}
+
+pub fn staged(dir: &Path) -> Result<HashSet<PathBuf>> {
+    let repo = Repository::discover(dir)?;
+    let tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(e) if e.code() == ErrorCode::UnbornBranch => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let diff = repo.diff_tree_to_index(tree.as_ref(), None, None)?;
+    paths(&repo, &diff)
+}
+
+pub fn root(dir: &Path) -> Result<PathBuf> {
+    workdir(&Repository::discover(dir)?)
+}
+
+// The content of `path` as it is in the index.
+pub fn staged_content(path: &Path) -> Result<Vec<u8>> {
+    let path = fs::canonicalize(path)?;
+    let repo = Repository::discover(path.parent().unwrap_or(&path))?;
+    let relative = path.strip_prefix(workdir(&repo)?)?;
+    let entry = repo
+        .index()?
+        .get_path(relative, 0)
+        .ok_or_else(|| anyhow!("{} is not in the index", relative.display()))?;
+    let blob = repo.find_blob(entry.id)?;
+    Ok(blob.content().to_vec())
+}
+
+// The branch checked out in the repository containing `dir`, if any.
+pub fn branch(dir: &Path) -> Result<Option<String>> {
+    let repo = Repository::discover(dir)?;
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(e) if e.code() == ErrorCode::UnbornBranch => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if !head.is_branch() {
+        return Ok(None);
+    }
+    Ok(Some(head.shorthand()?.to_string()))
+}
+
+fn workdir(repo: &Repository) -> Result<PathBuf> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("{} has no working tree", repo.path().display()))?;
+    Ok(fs::canonicalize(workdir)?)
+}
+
+fn paths(repo: &Repository, diff: &Diff) -> Result<HashSet<PathBuf>> {
+    let workdir = workdir(repo)?;
+
+    Ok(diff
+        .deltas()
+        .filter(|delta| delta.status() != Delta::Deleted)
+        .filter_map(|delta| delta.new_file().path())
+        .map(|path| workdir.join(path))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    #[test]
+    fn test_changed() {
+        let dir = std::env::temp_dir().join(format!("junk-git-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let repo = Repository::init(&dir).unwrap();
+        for name in ["a.json", "b.json", "c.json"] {
+            fs::write(dir.join(name), "{}").unwrap();
+        }
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.json")).unwrap();
+        index.add_path(Path::new("b.json")).unwrap();
+        index.write().unwrap();
+        assert_eq!(staged(&dir).unwrap().len(), 2);
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("junk", "junk@example.com").unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        assert!(staged(&dir).unwrap().is_empty());
+
+        let commit = repo.find_commit(commit).unwrap();
+        repo.branch("release/1.0", &commit, false).unwrap();
+        repo.set_head("refs/heads/release/1.0").unwrap();
+        assert_eq!(branch(&dir).unwrap().as_deref(), Some("release/1.0"));
+        repo.set_head_detached(commit.id()).unwrap();
+        assert_eq!(branch(&dir).unwrap(), None);
+
+        fs::write(dir.join("b.json"), "[]").unwrap();
+        let root = fs::canonicalize(&dir).unwrap();
+        assert_eq!(
+            changed_since(&dir, "HEAD").unwrap(),
+            HashSet::from([root.join("b.json"), root.join("c.json")])
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
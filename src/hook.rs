@@ -0,0 +1,92 @@
+use anyhow::{anyhow, bail, Result};
+use git2::Repository;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SCRIPT: &str = r#"#!/bin/sh
+# Installed by `junk hook install`.
+status=0
+junk xml sort-pom --check --staged || status=1
+junk json sort-policy --check --staged || status=1
+junk json sort-config --check --staged || status=1
+exit $status
+"#;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Args {
+    Install(Install),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Install {
+    #[arg(short, long)]
+    force: bool,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    match args {
+        Args::Install(args) => install(args),
+    }
+}
+
+fn install(args: Install) -> Result<()> {
+    let path = hooks_dir(Path::new("."))?.join("pre-commit");
+    if !args.force && path.exists() && fs::read_to_string(&path).ok().as_deref() != Some(SCRIPT) {
+        bail!(
+            "{} already exists, use --force to overwrite",
+            path.display()
+        );
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, SCRIPT)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    println!("installed {}", path.display());
+    Ok(())
+}
+
+fn hooks_dir(dir: &Path) -> Result<PathBuf> {
+    let repo = Repository::discover(dir)?;
+    let Ok(hooks) = repo.config()?.get_path("core.hooksPath") else {
+        return Ok(repo.path().join("hooks"));
+    };
+
+    if hooks.is_absolute() {
+        return Ok(hooks);
+    }
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("{} has no working tree", repo.path().display()))?;
+    Ok(workdir.join(hooks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooks_dir() {
+        let dir = std::env::temp_dir().join(format!("junk-hook-{}", std::process::id()));
+        let repo = Repository::init(&dir).unwrap();
+        assert_eq!(hooks_dir(&dir).unwrap(), repo.path().join("hooks"));
+
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", ".githooks")
+            .unwrap();
+        assert_eq!(
+            hooks_dir(&dir).unwrap(),
+            repo.workdir().unwrap().join(".githooks")
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,264 @@
+use crate::diagnostic::{Diagnostic, Diagnostics};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Keep {
+    Last,
+    First,
+}
+
+struct Member {
+    key: String,
+    start: usize,
+    end: usize,
+    comma: Option<usize>,
+}
+
+struct Duplicate {
+    path: String,
+    key: String,
+    offset: usize,
+    first: usize,
+}
+
+struct Scanner<'a> {
+    text: &'a [u8],
+    pos: usize,
+    keep: Keep,
+    duplicates: Vec<Duplicate>,
+    blank: Vec<(usize, usize)>,
+}
+
+// Finds object keys that appear more than once. Dropped members are blanked out with spaces rather
+// than cut, so every line and column of what is left stays where the user sees it.
+pub fn check<'a>(path: &Path, text: &'a str, keep: Option<Keep>) -> anyhow::Result<Cow<'a, str>> {
+    let mut scanner = Scanner {
+        text: text.as_bytes(),
+        pos: 0,
+        keep: keep.unwrap_or(Keep::Last),
+        duplicates: Vec::new(),
+        blank: Vec::new(),
+    };
+    // Malformed input is left to the real parser to report.
+    if scanner.value("").is_none() || scanner.duplicates.is_empty() {
+        return Ok(Cow::Borrowed(text));
+    }
+
+    if keep.is_none() {
+        scanner.duplicates.sort_by_key(|duplicate| duplicate.offset);
+        let diagnostics = scanner
+            .duplicates
+            .iter()
+            .map(|duplicate| {
+                let first = Diagnostic::at(path, text, duplicate.first, "");
+                let message = format!(
+                    "duplicate key `{}`, first defined at line {} column {}",
+                    duplicate.key, first.line, first.column
+                );
+                Diagnostic::at(path, text, duplicate.offset, &message).context(&*duplicate.path)
+            })
+            .collect();
+        return Err(Diagnostics(diagnostics).into());
+    }
+
+    let mut bytes = text.as_bytes().to_vec();
+    for (start, end) in scanner.blank {
+        for b in &mut bytes[start..end] {
+            if !matches!(b, b'\n' | b'\r') {
+                *b = b' ';
+            }
+        }
+    }
+    Ok(Cow::Owned(String::from_utf8(bytes)?))
+}
+
+impl Scanner<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        while self.text.get(self.pos)?.is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        (self.peek()? == b).then(|| self.pos += 1)
+    }
+
+    fn value(&mut self, path: &str) -> Option<()> {
+        match self.peek()? {
+            b'{' => self.object(path),
+            b'[' => self.array(path),
+            b'"' => self.string().map(|_| ()),
+            _ => {
+                let start = self.pos;
+                while self
+                    .text
+                    .get(self.pos)
+                    .is_some_and(|b| !b",]}".contains(b) && !b.is_ascii_whitespace())
+                {
+                    self.pos += 1;
+                }
+                (self.pos > start).then_some(())
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        loop {
+            match self.text.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => break,
+                _ => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        serde_json::from_slice(&self.text[start..self.pos]).ok()
+    }
+
+    fn array(&mut self, path: &str) -> Option<()> {
+        self.expect(b'[')?;
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Some(());
+        }
+
+        for i in 0.. {
+            self.value(&format!("{path}[{i}]"))?;
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => break,
+                _ => return None,
+            }
+        }
+        self.pos += 1;
+        Some(())
+    }
+
+    fn object(&mut self, path: &str) -> Option<()> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        if self.peek()? != b'}' {
+            loop {
+                self.peek()?;
+                let start = self.pos;
+                let key = self.string()?;
+                self.expect(b':')?;
+                match path {
+                    "" => self.value(&key)?,
+                    _ => self.value(&format!("{path}.{key}"))?,
+                }
+                let end = self.pos;
+
+                let comma = (self.peek()? == b',').then_some(self.pos);
+                members.push(Member {
+                    key,
+                    start,
+                    end,
+                    comma,
+                });
+                if comma.is_none() {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+        self.expect(b'}')?;
+
+        let mut seen = HashMap::new();
+        let mut survivors = HashMap::new();
+        for (i, member) in members.iter().enumerate() {
+            match seen.get(&member.key) {
+                Some(&first) => {
+                    let first: &Member = &members[first];
+                    self.duplicates.push(Duplicate {
+                        path: path.to_string(),
+                        key: member.key.clone(),
+                        offset: member.start,
+                        first: first.start,
+                    });
+                }
+                None => {
+                    seen.insert(member.key.clone(), i);
+                }
+            }
+            if self.keep == Keep::Last || !survivors.contains_key(&member.key) {
+                survivors.insert(member.key.clone(), i);
+            }
+        }
+        if seen.len() == members.len() {
+            return Some(());
+        }
+
+        // Every survivor but the last keeps the comma after it; everything else goes.
+        let last = survivors.values().max().copied();
+        for (i, member) in members.iter().enumerate() {
+            let survives = survivors.get(&member.key) == Some(&i);
+            if !survives {
+                self.blank.push((member.start, member.end));
+            }
+            if let Some(comma) = member.comma {
+                if !survives || Some(i) == last {
+                    self.blank.push((comma, comma + 1));
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"{
+    "a": 1,
+    "b": { "c": [1, { "d": 1, "d": 2 }] },
+    "a": 2
+}"#;
+
+    #[test]
+    fn test_check() {
+        let path = Path::new("a.json");
+        let error = check(path, TEXT, None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"a.json:3:31: duplicate key `d`, first defined at line 3 column 23
+  in b.c[1]
+  |
+3 |     "b": { "c": [1, { "d": 1, "d": 2 }] },
+  |                               ^
+a.json:4:5: duplicate key `a`, first defined at line 2 column 5
+  |
+4 |     "a": 2
+  |     ^"#
+        );
+
+        let last = check(path, TEXT, Some(Keep::Last)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&last).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"b": {"c": [1, {"d": 2}]}, "a": 2})
+        );
+        assert_eq!(last.lines().count(), TEXT.lines().count());
+
+        let first = check(path, TEXT, Some(Keep::First)).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"a": 1, "b": {"c": [1, {"d": 1}]}})
+        );
+
+        assert!(matches!(
+            check(path, r#"{"a": 1}"#, None),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert!(matches!(
+            check(path, r#"{"a": }"#, None),
+            Ok(Cow::Borrowed(_))
+        ));
+    }
+}
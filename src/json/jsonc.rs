@@ -0,0 +1,441 @@
+use super::duplicate::Keep;
+use crate::diagnostic::Diagnostic;
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::path::Path;
+
+// A JSON document with its comments, as VS Code and TypeScript read it.
+#[derive(Debug)]
+pub struct Document<'a> {
+    pub leading: Vec<&'a str>,
+    pub root: Node<'a>,
+    pub trailing: Vec<&'a str>,
+}
+
+#[derive(Debug)]
+pub enum Node<'a> {
+    Object {
+        items: Vec<Item<'a>>,
+        dangling: Vec<&'a str>,
+    },
+    Array {
+        items: Vec<Item<'a>>,
+        dangling: Vec<&'a str>,
+        inline: bool,
+    },
+    Scalar(&'a str),
+}
+
+// A member of an object or an element of an array, with the comments around it.
+#[derive(Debug)]
+pub struct Item<'a> {
+    pub offset: usize,
+    pub comments: Vec<Cow<'a, str>>,
+    pub key: Option<Cow<'a, str>>,
+    pub value: Node<'a>,
+    pub trailing: Vec<&'a str>,
+    // Whether a blank line goes before it.
+    pub blank: bool,
+}
+
+// Syntax errors, duplicate keys and values that don't fit `T` are reported from a copy without
+// comments and trailing commas, so that they read the same as for plain JSON.
+pub fn parse<'a, T: DeserializeOwned>(
+    path: &'a Path,
+    text: &'a str,
+    keep: Option<Keep>,
+) -> Result<(Document<'a>, T)> {
+    let plain = super::strip_comments(text)?;
+    let value = super::from_str(path, &super::strip_trailing_commas(&plain)?, keep)?;
+
+    let mut parser = Parser { path, text, pos: 0 };
+    let leading = parser.comments();
+    let mut root = parser.value()?;
+    let trailing = parser.comments();
+    if parser.pos < text.len() {
+        return Err(parser.error("trailing characters").into());
+    }
+    if let Some(keep) = keep {
+        root.dedup_keys(keep);
+    }
+
+    let document = Document {
+        leading,
+        root,
+        trailing,
+    };
+    Ok((document, value))
+}
+
+impl Document<'_> {
+    pub fn write(&self, indent: &str) -> String {
+        let mut output = String::new();
+        for comment in &self.leading {
+            output.push_str(comment);
+            output.push('\n');
+        }
+        self.root.write(&mut output, indent, 0);
+        output.push('\n');
+        for comment in &self.trailing {
+            output.push_str(comment);
+            output.push('\n');
+        }
+        output
+    }
+}
+
+// Keeps the first of each repeated entry; the comments of the others move onto it.
+pub fn dedup<K: PartialEq>(items: &mut Vec<Item>, key: impl Fn(&Item) -> Option<K>) {
+    let mut kept: Vec<Item> = Vec::new();
+    for item in items.drain(..) {
+        let value = key(&item);
+        match kept
+            .iter_mut()
+            .find(|other| value.is_some() && key(other) == value)
+        {
+            Some(other) => other.comments.extend(item.comments),
+            None => kept.push(item),
+        }
+    }
+    *items = kept;
+}
+
+impl Item<'_> {
+    pub fn name(&self) -> Option<String> {
+        serde_json::from_str(self.key.as_deref()?).ok()
+    }
+}
+
+impl<'a> Node<'a> {
+    pub fn get(&self, name: &str) -> Option<&Node<'a>> {
+        match self {
+            Node::Object { items, .. } => items
+                .iter()
+                .find(|item| item.name().as_deref() == Some(name))
+                .map(|item| &item.value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Node<'a>> {
+        match self {
+            Node::Object { items, .. } => items
+                .iter_mut()
+                .find(|item| item.name().as_deref() == Some(name))
+                .map(|item| &mut item.value),
+            _ => None,
+        }
+    }
+
+    // Drops repeated keys the way `duplicate::check` does, along with their comments.
+    fn dedup_keys(&mut self, keep: Keep) {
+        let items = match self {
+            Node::Object { items, .. } => {
+                let mut seen = HashSet::new();
+                if keep == Keep::Last {
+                    items.reverse();
+                }
+                items.retain(|item| seen.insert(item.name()));
+                if keep == Keep::Last {
+                    items.reverse();
+                }
+                items
+            }
+            Node::Array { items, .. } => items,
+            Node::Scalar(_) => return,
+        };
+        for item in items {
+            item.value.dedup_keys(keep);
+        }
+    }
+
+    // Puts `fields` first, in that order, and the other keys after them alphabetically.
+    pub fn order(&mut self, fields: &[&str]) {
+        if let Node::Object { items, .. } = self {
+            items.sort_by_cached_key(|item| {
+                let name = item.name().unwrap_or_default();
+                let i = fields.iter().position(|field| *field == name);
+                (i.unwrap_or(fields.len()), name)
+            });
+        }
+    }
+
+    // Sorts the keys of this and every nested object.
+    pub fn sort_keys(&mut self) {
+        let items = match self {
+            Node::Object { items, .. } => {
+                items.sort_by_cached_key(Item::name);
+                items
+            }
+            Node::Array { items, .. } => items,
+            Node::Scalar(_) => return,
+        };
+        for item in items {
+            item.value.sort_keys();
+        }
+    }
+
+    // Writes every array over several lines, the way `PrettyFormatter` does.
+    pub fn expand(&mut self) {
+        let items = match self {
+            Node::Object { items, .. } => items,
+            Node::Array { items, inline, .. } => {
+                *inline = false;
+                items
+            }
+            Node::Scalar(_) => return,
+        };
+        for item in items {
+            item.value.expand();
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            Node::Object { items, .. } => Value::Object(
+                items
+                    .iter()
+                    .map(|item| (item.name().unwrap_or_default(), item.value.to_value()))
+                    .collect(),
+            ),
+            Node::Array { items, .. } => {
+                Value::Array(items.iter().map(|item| item.value.to_value()).collect())
+            }
+            Node::Scalar(_) => self.scalar().unwrap_or_default(),
+        }
+    }
+
+    pub fn scalar(&self) -> Option<Value> {
+        match self {
+            Node::Scalar(raw) => serde_json::from_str(raw).ok(),
+            _ => None,
+        }
+    }
+
+    fn write(&self, out: &mut String, indent: &str, depth: usize) {
+        let (items, dangling, open, close) = match self {
+            Node::Scalar(raw) => return out.push_str(raw),
+            Node::Object { items, dangling } => (items, dangling, '{', '}'),
+            Node::Array {
+                items,
+                dangling,
+                inline,
+            } => {
+                let plain = items
+                    .iter()
+                    .all(|item| item.comments.is_empty() && item.trailing.is_empty());
+                if *inline && plain && dangling.is_empty() {
+                    out.push('[');
+                    for (i, item) in items.iter().enumerate() {
+                        if i > 0 {
+                            out.push_str(", ");
+                        }
+                        item.value.write(out, indent, depth);
+                    }
+                    return out.push(']');
+                }
+                (items, dangling, '[', ']')
+            }
+        };
+
+        out.push(open);
+        if items.is_empty() && dangling.is_empty() {
+            return out.push(close);
+        }
+        out.push('\n');
+
+        let prefix = indent.repeat(depth + 1);
+        for (i, item) in items.iter().enumerate() {
+            if item.blank {
+                out.push('\n');
+            }
+            for comment in &item.comments {
+                out.push_str(&format!("{prefix}{comment}\n"));
+            }
+            out.push_str(&prefix);
+            if let Some(key) = &item.key {
+                out.push_str(key);
+                out.push_str(": ");
+            }
+            item.value.write(out, indent, depth + 1);
+            if i + 1 < items.len() {
+                out.push(',');
+            }
+            for comment in &item.trailing {
+                out.push(' ');
+                out.push_str(comment);
+            }
+            out.push('\n');
+        }
+        for comment in dangling {
+            out.push_str(&format!("{prefix}{comment}\n"));
+        }
+        out.push_str(&indent.repeat(depth));
+        out.push(close);
+    }
+}
+
+struct Parser<'a> {
+    path: &'a Path,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> Diagnostic {
+        Diagnostic::at(self.path, self.text, self.pos, message)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn comment(&mut self) -> Option<&'a str> {
+        let rest = &self.text[self.pos..];
+        let len = if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            body.find("*/").map_or(rest.len(), |i| i + 4)
+        } else {
+            return None;
+        };
+        self.pos += len;
+        Some(rest[..len].trim_end())
+    }
+
+    fn comments(&mut self) -> Vec<&'a str> {
+        let mut comments = Vec::new();
+        loop {
+            while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+            match self.comment() {
+                Some(comment) => comments.push(comment),
+                None => return comments,
+            }
+        }
+    }
+
+    // Comments on the same line as what precedes them.
+    fn trailing(&mut self) -> Vec<&'a str> {
+        let mut comments = Vec::new();
+        loop {
+            while matches!(self.peek(), Some(b' ' | b'\t')) {
+                self.pos += 1;
+            }
+            match self.comment() {
+                Some(comment) => comments.push(comment),
+                None => return comments,
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node<'a>, Diagnostic> {
+        match self.peek() {
+            Some(b'{') => self.items(b'}'),
+            Some(b'[') => self.items(b']'),
+            Some(b'"') => self.string().map(Node::Scalar),
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|b| !b",]}/".contains(&b) && !b.is_ascii_whitespace())
+                {
+                    self.pos += 1;
+                }
+                match self.pos > start {
+                    true => Ok(Node::Scalar(&self.text[start..self.pos])),
+                    false => Err(self.error("expected value")),
+                }
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<&'a str, Diagnostic> {
+        let start = self.pos;
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some(b'\\') => self.pos += 2,
+                Some(b'"') => break,
+                Some(_) => self.pos += 1,
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+        self.pos += 1;
+        Ok(&self.text[start..self.pos])
+    }
+
+    fn items(&mut self, close: u8) -> Result<Node<'a>, Diagnostic> {
+        let start = self.pos;
+        self.pos += 1;
+
+        let mut items = Vec::new();
+        let mut pending = Vec::new();
+        loop {
+            pending.extend(self.comments());
+            if self.peek() == Some(close) {
+                self.pos += 1;
+                break;
+            }
+
+            let offset = self.pos;
+            let key = match close {
+                b'}' if self.peek() == Some(b'"') => {
+                    let key = self.string()?;
+                    pending.extend(self.comments());
+                    if self.peek() != Some(b':') {
+                        return Err(self.error("expected `:`"));
+                    }
+                    self.pos += 1;
+                    pending.extend(self.comments());
+                    Some(key.into())
+                }
+                b'}' => return Err(self.error("expected key")),
+                _ => None,
+            };
+            let value = self.value()?;
+
+            let mut trailing = self.trailing();
+            let after = self.comments();
+            let more = self.peek() == Some(b',');
+            if more {
+                self.pos += 1;
+                trailing.extend(self.trailing());
+            }
+            items.push(Item {
+                offset,
+                comments: pending.drain(..).map(Cow::from).collect(),
+                key,
+                value,
+                trailing,
+                blank: false,
+            });
+            pending.extend(after);
+
+            if !more {
+                pending.extend(self.comments());
+                if self.peek() != Some(close) {
+                    return Err(self.error(&format!("expected `,` or `{}`", close as char)));
+                }
+                self.pos += 1;
+                break;
+            }
+        }
+
+        Ok(match close {
+            b'}' => Node::Object {
+                items,
+                dangling: pending,
+            },
+            _ => Node::Array {
+                items,
+                dangling: pending,
+                inline: !self.text[start..self.pos].contains('\n'),
+            },
+        })
+    }
+}
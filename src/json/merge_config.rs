@@ -0,0 +1,237 @@
+use super::duplicate::Keep;
+use super::jsonc::{self, Document, Node};
+use super::schema::Schema;
+use super::sort_config::{self, Entries, Key};
+use crate::batch;
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str;
+
+type Config = BTreeMap<Key, Value>;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    base: PathBuf,
+
+    #[arg(required = true)]
+    overlays: Vec<PathBuf>,
+
+    #[arg(long)]
+    prune_redundant: bool,
+
+    #[arg(long, requires = "prune_redundant")]
+    check: bool,
+
+    #[arg(long, value_name = "DIR", requires = "prune_redundant")]
+    extensions_dir: Vec<PathBuf>,
+
+    #[arg(long, value_enum, value_name = "KEEP")]
+    allow_duplicates: Option<Keep>,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let keep = args.allow_duplicates;
+    let mut config = read(&args.base, keep)?;
+    if !args.prune_redundant {
+        for path in &args.overlays {
+            merge(&mut config, read(path, keep)?);
+        }
+        let mut output = Vec::new();
+        sort_config::write(&config, &mut output)?;
+        io::stdout().write_all(&output)?;
+        return Ok(());
+    }
+
+    let schema = Schema::load(&args.extensions_dir)?;
+    let options = batch::Options {
+        check: args.check,
+        ..batch::Options::default()
+    };
+    let mut outcomes = Vec::new();
+    for path in &args.overlays {
+        let mut overlay = None;
+        let outcome = batch::process(path, &options, |path, input| {
+            let text = str::from_utf8(input)?;
+            let (mut document, parsed) = parse(path, text, keep)?;
+            prune(&config, &schema, &mut document.root);
+            overlay = Some(parsed);
+            Ok(document.write(super::indent_of(text)).into_bytes())
+        });
+        // Each overlay is judged against everything layered beneath it.
+        if let Some(overlay) = overlay {
+            merge(&mut config, overlay);
+        }
+        outcomes.push((path, outcome));
+    }
+    batch::report(outcomes, args.check)
+}
+
+fn read(path: &Path, keep: Option<Keep>) -> Result<Config> {
+    Ok(parse(path, &fs::read_to_string(path)?, keep)?.1)
+}
+
+fn parse<'a>(path: &'a Path, text: &'a str, keep: Option<Keep>) -> Result<(Document<'a>, Config)> {
+    let (document, entries): (_, Entries) = jsonc::parse(path, text, keep)?;
+    let mut warnings = Vec::new();
+    let config = entries.collect(&mut warnings);
+    batch::report_warnings(path, &warnings);
+    Ok((document, config))
+}
+
+// Later layers win, except that VS Code merges object values and language blocks one level deep
+// instead of replacing them.
+fn merge(config: &mut Config, overlay: Config) {
+    for (key, value) in overlay {
+        let Some(base) = config.get_mut(&key) else {
+            config.insert(key, value);
+            continue;
+        };
+
+        match (&key, base, value) {
+            (Key::Lang(_), Value::Object(block), Value::Object(settings)) => {
+                for (name, value) in settings {
+                    match block.get_mut(&name) {
+                        Some(base) => merge_value(base, value),
+                        None => {
+                            block.insert(name, value);
+                        }
+                    }
+                }
+            }
+            (_, base, value) => merge_value(base, value),
+        }
+    }
+}
+
+fn merge_value(base: &mut Value, value: Value) {
+    match (base, value) {
+        (Value::Object(base), Value::Object(value)) => base.extend(value),
+        (base, value) => *base = value,
+    }
+}
+
+// Removes the settings of an overlay that merely repeat what is beneath it, along with their
+// comments.
+fn prune(config: &Config, schema: &Schema, root: &mut Node) {
+    let Node::Object { items, .. } = root else {
+        return;
+    };
+    items.retain_mut(|item| {
+        let Some(key) = item.name().and_then(|name| name.parse::<Key>().ok()) else {
+            return true;
+        };
+        match (&key, &mut item.value) {
+            (Key::Config(name), value) => !reduce(
+                config.get(&key).or_else(|| schema.default_value(name)),
+                value,
+            ),
+            (
+                Key::Lang(_),
+                Node::Object {
+                    items: settings, ..
+                },
+            ) => {
+                let block = config.get(&key).and_then(Value::as_object);
+                settings.retain_mut(|setting| {
+                    let base = block.and_then(|block| block.get(&setting.name()?));
+                    !reduce(base, &mut setting.value)
+                });
+                !settings.is_empty()
+            }
+            (Key::Lang(_), _) => true,
+        }
+    });
+}
+
+// Takes out of `node` everything `base` already provides, and tells whether nothing is left.
+fn reduce(base: Option<&Value>, node: &mut Node) -> bool {
+    let Some(base) = base else {
+        return false;
+    };
+    if *base == node.to_value() {
+        return true;
+    }
+    match (base, node) {
+        (Value::Object(base), Node::Object { items, .. }) => {
+            items.retain(|item| {
+                item.name().and_then(|name| base.get(&name)) != Some(&item.value.to_value())
+            });
+            items.is_empty()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r#"{
+        // shared baseline
+        "editor.rulers": [100],
+        "files.exclude": {"**/.git": true, "target": true},
+        "[rust]": {"editor.formatOnSave": true, "editor.tabSize": 4}
+    }"#;
+
+    const OVERLAY: &str = r#"{
+        "editor.rulers": [100],
+        "editor.tabSize": 4,
+        // wrap long lines
+        "editor.wordWrap": "on",
+        "files.exclude": {"target": true, "node_modules": true},
+        "[rust][toml]": {"editor.formatOnSave": true},
+        "[rust]": {"editor.formatOnSave": true, "editor.tabSize": 2},
+    }"#;
+
+    #[test]
+    fn test_merge() {
+        let path = Path::new("settings.json");
+        let mut config = parse(path, BASE, None).unwrap().1;
+        merge(&mut config, parse(path, OVERLAY, None).unwrap().1);
+
+        let mut output = Vec::new();
+        sort_config::write(&config, &mut output).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&output).unwrap(),
+            serde_json::json!({
+                "[rust]": {"editor.formatOnSave": true, "editor.tabSize": 2},
+                "[rust][toml]": {"editor.formatOnSave": true},
+                "editor.rulers": [100],
+                "editor.tabSize": 4,
+                "editor.wordWrap": "on",
+                "files.exclude": {"**/.git": true, "node_modules": true, "target": true},
+            })
+        );
+    }
+
+    #[test]
+    fn test_prune() {
+        let path = Path::new("settings.json");
+        let config = parse(path, BASE, None).unwrap().1;
+        let schema = Schema::load(&[] as &[&Path]).unwrap();
+
+        let (mut document, _) = parse(path, OVERLAY, None).unwrap();
+        prune(&config, &schema, &mut document.root);
+        assert_eq!(
+            document.write("    "),
+            r#"{
+    // wrap long lines
+    "editor.wordWrap": "on",
+    "files.exclude": {
+        "node_modules": true
+    },
+    "[rust][toml]": {
+        "editor.formatOnSave": true
+    },
+    "[rust]": {
+        "editor.tabSize": 2
+    }
+}
+"#
+        );
+    }
+}
@@ -1,15 +1,138 @@
-mod sort_config;
-mod sort_policy;
+pub(crate) mod duplicate;
+pub(crate) mod jsonc;
+pub(crate) mod merge_config;
+pub(crate) mod schema;
+pub(crate) mod sort;
+pub(crate) mod sort_config;
+pub(crate) mod sort_package;
+pub(crate) mod sort_policy;
+pub(crate) mod sort_tsconfig;
+pub(crate) mod vscode;
+
+use crate::diagnostic::Diagnostic;
+use duplicate::Keep;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use std::path::Path;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Args {
+    MergeConfig(merge_config::Args),
+    Sort(sort::Args),
     SortConfig(sort_config::Args),
+    SortPackage(sort_package::Args),
     SortPolicy(sort_policy::Args),
+    SortTsconfig(sort_tsconfig::Args),
 }
 
 pub fn main(args: Args) -> anyhow::Result<()> {
     match args {
+        Args::MergeConfig(args) => merge_config::main(args),
+        Args::Sort(args) => sort::main(args),
         Args::SortConfig(args) => sort_config::main(args),
+        Args::SortPackage(args) => sort_package::main(args),
         Args::SortPolicy(args) => sort_policy::main(args),
+        Args::SortTsconfig(args) => sort_tsconfig::main(args),
+    }
+}
+
+// VS Code reads its settings as JSON with comments. Comments are blanked out rather than removed so
+// that diagnostics still point at the right place.
+fn strip_comments(text: &str) -> anyhow::Result<Cow<'_, str>> {
+    let bytes = text.as_bytes();
+    let mut output: Option<Vec<u8>> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let end = match &bytes[i..] {
+            [b'"', ..] => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i += 1;
+                continue;
+            }
+            [b'/', b'/', rest @ ..] => rest
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |n| i + 2 + n),
+            [b'/', b'*', rest @ ..] => rest
+                .windows(2)
+                .position(|w| w == b"*/")
+                .map_or(bytes.len(), |n| i + 4 + n),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        let output = output.get_or_insert_with(|| bytes.to_vec());
+        for b in &mut output[i..end] {
+            if !matches!(b, b'\n' | b'\r') {
+                *b = b' ';
+            }
+        }
+        i = end;
     }
+
+    match output {
+        Some(output) => Ok(Cow::Owned(String::from_utf8(output)?)),
+        None => Ok(Cow::Borrowed(text)),
+    }
+}
+
+// TypeScript accepts a comma after the last member or element; blank those out the same way.
+fn strip_trailing_commas(text: &str) -> anyhow::Result<Cow<'_, str>> {
+    let bytes = text.as_bytes();
+    let mut output: Option<Vec<u8>> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b',' => {
+                let next = bytes[i + 1..].iter().find(|b| !b.is_ascii_whitespace());
+                if matches!(next, Some(b'}' | b']')) {
+                    output.get_or_insert_with(|| bytes.to_vec())[i] = b' ';
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    match output {
+        Some(output) => Ok(Cow::Owned(String::from_utf8(output)?)),
+        None => Ok(Cow::Borrowed(text)),
+    }
+}
+
+// The indentation of the first indented line, so that rewritten files keep their style.
+fn indent_of(text: &str) -> &str {
+    text.lines()
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+}
+
+pub(crate) fn from_str<T: DeserializeOwned>(
+    path: &Path,
+    text: &str,
+    keep: Option<Keep>,
+) -> anyhow::Result<T> {
+    let text = &duplicate::check(path, text, keep)?;
+    let mut de = serde_json::Deserializer::from_str(text);
+    let value = serde_path_to_error::deserialize(&mut de).map_err(|e| {
+        // The root is `.`, and a key that wasn't read yet is `?`; neither says more than the location.
+        let context = e.path().to_string();
+        let context = context.strip_suffix('?').unwrap_or(&context);
+        Diagnostic::json(path, text, e.inner()).context(context.trim_end_matches('.'))
+    })?;
+    de.end().map_err(|e| Diagnostic::json(path, text, &e))?;
+
+    Ok(value)
 }
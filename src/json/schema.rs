@@ -0,0 +1,318 @@
+use super::duplicate::Keep;
+use crate::diagnostic::{Diagnostic, Diagnostics};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+// A subset of the settings VS Code itself contributes, in the same shape as an extension's
+// `contributes.configuration`.
+const BUNDLED: &str = include_str!("vscode-settings.json");
+
+#[derive(Debug, Default, Deserialize)]
+struct Configuration {
+    #[serde(default)]
+    properties: BTreeMap<String, Property>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Property {
+    #[serde(rename = "type")]
+    type_: Option<Types>,
+    #[serde(rename = "enum")]
+    enum_: Option<Vec<Value>>,
+    default: Option<Value>,
+    deprecation_message: Option<String>,
+    markdown_deprecation_message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Types {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    contributes: Contributes,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Contributes {
+    #[serde(default)]
+    configuration: Option<Configurations>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Configurations {
+    Many(Vec<Configuration>),
+    One(Configuration),
+}
+
+#[derive(Debug, Default)]
+pub struct Schema {
+    properties: BTreeMap<String, Property>,
+    namespaces: BTreeSet<String>,
+}
+
+impl Schema {
+    pub fn load(dirs: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut schema = Schema::default();
+        let bundled: Configuration = serde_json::from_str(BUNDLED)?;
+        let core = bundled
+            .properties
+            .keys()
+            .map(|key| namespace(key).to_string())
+            .collect::<BTreeSet<_>>();
+        schema.add(bundled);
+
+        for dir in dirs {
+            let mut manifests = Vec::new();
+            for entry in fs::read_dir(dir.as_ref())
+                .with_context(|| format!("failed to read {}", dir.as_ref().display()))?
+            {
+                let path = entry?.path().join("package.json");
+                if path.is_file() {
+                    manifests.push(path);
+                }
+            }
+            manifests.sort();
+
+            for path in manifests {
+                let manifest: Manifest = serde_json::from_slice(&fs::read(&path)?)
+                    .with_context(|| format!("failed to parse {}", path.display()))?;
+                match manifest.contributes.configuration {
+                    Some(Configurations::One(configuration)) => schema.add(configuration),
+                    Some(Configurations::Many(configurations)) => {
+                        configurations.into_iter().for_each(|c| schema.add(c))
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        // The bundled settings are only a subset of VS Code's own, but an extension lists all of
+        // its settings, so only keys in an extension's namespace can be called unknown.
+        schema.namespaces = schema
+            .properties
+            .keys()
+            .map(|key| namespace(key))
+            .filter(|namespace| !core.contains(*namespace))
+            .map(str::to_string)
+            .collect();
+        Ok(schema)
+    }
+
+    fn add(&mut self, configuration: Configuration) {
+        self.properties.extend(configuration.properties);
+    }
+
+    pub fn default_value(&self, key: &str) -> Option<&Value> {
+        self.properties.get(key)?.default.as_ref()
+    }
+
+    pub fn validate(&self, path: &Path, input: &[u8]) -> Result<()> {
+        let plain = super::strip_comments(std::str::from_utf8(input)?)?;
+        let text = super::strip_trailing_commas(&plain)?;
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let (settings, from) = match name {
+            "extensions.json" | "keybindings.json" | "launch.json" | "tasks.json" => return Ok(()),
+            _ if name.ends_with(".code-workspace") => {
+                let mut workspace: Map<String, Value> =
+                    super::from_str(path, &text, Some(Keep::Last))?;
+                match workspace.remove("settings") {
+                    Some(Value::Object(settings)) => (settings, offset(&text, 0, "settings")),
+                    _ => return Ok(()),
+                }
+            }
+            _ => (super::from_str(path, &text, Some(Keep::Last))?, 0),
+        };
+
+        let mut diagnostics = Vec::new();
+        for (key, value) in &settings {
+            let at = offset(&text, from, key);
+            match value {
+                // Language blocks hold overrides of ordinary settings.
+                Value::Object(overrides) if key.starts_with('[') => {
+                    for (key, value) in overrides {
+                        let at = offset(&text, at, key);
+                        if let Some(message) = self.check(key, value) {
+                            diagnostics.push(Diagnostic::at(path, &text, at, &message));
+                        }
+                    }
+                }
+                _ => {
+                    if let Some(message) = self.check(key, value) {
+                        diagnostics.push(Diagnostic::at(path, &text, at, &message));
+                    }
+                }
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        match diagnostics.is_empty() {
+            true => Ok(()),
+            false => Err(Diagnostics(diagnostics).into()),
+        }
+    }
+
+    fn check(&self, key: &str, value: &Value) -> Option<String> {
+        let Some(property) = self.properties.get(key) else {
+            // Settings of extensions we know nothing about are not ours to judge.
+            return self
+                .namespaces
+                .contains(namespace(key))
+                .then(|| format!("unknown setting `{key}`"));
+        };
+
+        if let Some(message) = property
+            .deprecation_message
+            .as_ref()
+            .or(property.markdown_deprecation_message.as_ref())
+        {
+            return Some(format!("`{key}` is deprecated: {message}"));
+        }
+
+        if let Some(types) = &property.type_ {
+            let types = match types {
+                Types::One(t) => std::slice::from_ref(t),
+                Types::Many(types) => types.as_slice(),
+            };
+            if !types.iter().any(|t| matches(t, value)) {
+                return Some(format!(
+                    "expected {} for `{key}`, found {}",
+                    types.join(" or "),
+                    type_of(value)
+                ));
+            }
+        }
+
+        if let Some(values) = &property.enum_ {
+            if !values.contains(value) {
+                let values = values.iter().map(Value::to_string).collect::<Vec<_>>();
+                return Some(format!(
+                    "invalid value {value} for `{key}`, expected one of {}",
+                    values.join(", ")
+                ));
+            }
+        }
+
+        None
+    }
+}
+
+fn namespace(key: &str) -> &str {
+    key.split_once('.').map_or(key, |(prefix, _)| prefix)
+}
+
+fn matches(t: &str, value: &Value) -> bool {
+    match t {
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        _ => t == type_of(value),
+    }
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// Position of the member named `key` at or after `from`, for pointing diagnostics at it.
+fn offset(text: &str, from: usize, key: &str) -> usize {
+    let quoted = serde_json::to_string(key).unwrap_or_default();
+    let mut at = from;
+    while let Some(i) = text[at..].find(&quoted) {
+        let start = at + i;
+        at = start + quoted.len();
+        if text[at..].trim_start().starts_with(':') {
+            return start;
+        }
+    }
+    from
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"{
+    // comment
+    "editor.fontSize": "14",
+    "editor.wordWrap": "sometimes",
+    "editor.tabSize": 4,
+    "editor.quickSuggestionsDelay": 10,
+    "telemetry.enableTelemetry": false,
+    "[rust]": {
+        "editor.formatOnSave": 1
+    },
+    "rust-analyzer.check.command": "clippy",
+    "example.enabeld": true,
+    "example.enabled": "yes"
+}"#;
+
+    #[test]
+    fn test_validate() {
+        let dir = std::env::temp_dir().join(format!("junk-schema-{}", std::process::id()));
+        fs::create_dir_all(dir.join("example")).unwrap();
+        fs::write(
+            dir.join("example/package.json"),
+            r#"{"contributes": {"configuration": [{"properties": {"example.enabled": {"type": "boolean"}}}]}}"#,
+        )
+        .unwrap();
+
+        let schema = Schema::load(&[&dir]).unwrap();
+        let error = schema
+            .validate(Path::new("settings.json"), TEXT.as_bytes())
+            .unwrap_err();
+        let diagnostics = &error.downcast_ref::<Diagnostics>().unwrap().0;
+        let messages = diagnostics
+            .iter()
+            .map(|d| (d.line, d.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                (3, "expected number for `editor.fontSize`, found string"),
+                (
+                    4,
+                    r#"invalid value "sometimes" for `editor.wordWrap`, expected one of "off", "on", "wordWrapColumn", "bounded""#
+                ),
+                (
+                    7,
+                    "`telemetry.enableTelemetry` is deprecated: This setting is deprecated. Use `telemetry.telemetryLevel` instead."
+                ),
+                (9, "expected boolean for `editor.formatOnSave`, found number"),
+                (12, "unknown setting `example.enabeld`"),
+                (13, "expected boolean for `example.enabled`, found string"),
+            ]
+        );
+
+        // Only some of VS Code's own settings are bundled, so misspelling one goes unreported.
+        assert!(schema
+            .validate(Path::new("settings.json"), br#"{"editor.fontSzie": 14,}"#)
+            .is_ok());
+
+        assert!(Schema::load(&[] as &[&Path])
+            .unwrap()
+            .validate(Path::new("launch.json"), b"[]")
+            .is_ok());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,307 @@
+use super::duplicate::Keep;
+use super::jsonc::{self, Node};
+use crate::batch;
+use crate::serde_helper::string;
+use anyhow::{anyhow, Error, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str;
+
+// Files that have no sorter of their own; anything else has to be named explicitly.
+const PATTERNS: &[&str] = &[
+    ".eslintrc.json",
+    ".renovaterc.json",
+    "composer.json",
+    "renovate.json",
+];
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+
+    #[arg(long)]
+    sort_arrays: bool,
+
+    #[arg(long, value_enum, value_name = "KEEP")]
+    allow_duplicates: Option<Keep>,
+
+    #[command(flatten)]
+    options: batch::Options,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let mut rules = match &args.rules {
+        Some(path) => {
+            let text = fs::read_to_string(path)?;
+            super::from_str(path, &super::strip_comments(&text)?, None)?
+        }
+        None => Rules::default(),
+    };
+    rules.sort_arrays = args.sort_arrays;
+
+    batch::run(&args.paths, &args.options, PATTERNS, |path, input| {
+        sort(path, input, &rules, args.allow_duplicates)
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rules {
+    #[serde(default)]
+    rules: Vec<Rule>,
+
+    #[serde(skip)]
+    sort_arrays: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    #[serde(deserialize_with = "string::deserialize")]
+    path: Pattern,
+
+    #[serde(default)]
+    first: Vec<String>,
+
+    array: Option<Order>,
+
+    by: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Order {
+    Keep,
+    Sort,
+}
+
+// A JSON Pointer where a `*` segment matches any single key or index and `**` any number of them.
+#[derive(Debug, PartialEq, Eq)]
+struct Pattern(Vec<String>);
+
+impl std::str::FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Ok(Pattern(Vec::new()));
+        }
+        let rest = s
+            .strip_prefix('/')
+            .ok_or_else(|| anyhow!("path must be empty or start with `/`"))?;
+        Ok(Pattern(
+            rest.split('/')
+                .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+                .collect(),
+        ))
+    }
+}
+
+impl Pattern {
+    fn matches(&self, path: &[String]) -> bool {
+        fn matches(pattern: &[String], path: &[String]) -> bool {
+            match (pattern.split_first(), path.split_first()) {
+                (None, None) => true,
+                (Some((first, rest)), _) if first == "**" => {
+                    matches(rest, path) || (!path.is_empty() && matches(pattern, &path[1..]))
+                }
+                (Some((first, rest)), Some((segment, path))) => {
+                    (first == "*" || first == segment) && matches(rest, path)
+                }
+                _ => false,
+            }
+        }
+
+        matches(&self.0, path)
+    }
+}
+
+// What the rules say about one location; later rules override earlier ones.
+#[derive(Debug, Default)]
+struct Applied<'a> {
+    first: &'a [String],
+    array: Option<Order>,
+    by: Option<&'a str>,
+}
+
+impl Rules {
+    fn at(&self, path: &[String]) -> Applied<'_> {
+        let mut applied = Applied::default();
+        for rule in self.rules.iter().filter(|rule| rule.path.matches(path)) {
+            if !rule.first.is_empty() {
+                applied.first = &rule.first;
+            }
+            if rule.array.is_some() {
+                applied.array = rule.array;
+            }
+            if let Some(by) = &rule.by {
+                applied.by = Some(by);
+            }
+        }
+        applied
+    }
+}
+
+pub fn sort(path: &Path, input: &[u8], rules: &Rules, keep: Option<Keep>) -> Result<Vec<u8>> {
+    let text = str::from_utf8(input)?;
+    let (mut document, _): (_, Value) = jsonc::parse(path, text, keep)?;
+    arrange(&mut document.root, &mut Vec::new(), rules);
+    Ok(document.write(super::indent_of(text)).into_bytes())
+}
+
+fn arrange(node: &mut Node, path: &mut Vec<String>, rules: &Rules) {
+    let applied = rules.at(path);
+    match node {
+        Node::Object { items, .. } => {
+            // Keys are sorted, except that pinned ones go first.
+            items.sort_by_cached_key(|item| {
+                let name = item.name().unwrap_or_default();
+                let pinned = applied.first.iter().position(|first| *first == name);
+                (pinned.unwrap_or(usize::MAX), name)
+            });
+            for item in items {
+                path.push(item.name().unwrap_or_default());
+                arrange(&mut item.value, path, rules);
+                path.pop();
+            }
+        }
+        Node::Array { items, .. } => {
+            let scalars = items.iter().all(|item| item.value.scalar().is_some());
+            let order = applied.array.unwrap_or(match applied.by {
+                Some(_) => Order::Sort,
+                None if rules.sort_arrays && scalars => Order::Sort,
+                None => Order::Keep,
+            });
+            if order == Order::Sort {
+                let mut keyed = items
+                    .drain(..)
+                    .map(|item| (item.value.to_value(), item))
+                    .collect::<Vec<_>>();
+                match applied.by {
+                    Some(by) => keyed.sort_by(|(a, _), (b, _)| match (a.get(by), b.get(by)) {
+                        (Some(a), Some(b)) => compare(a, b),
+                        (a, b) => b.is_some().cmp(&a.is_some()),
+                    }),
+                    None => keyed.sort_by(|(a, _), (b, _)| compare(a, b)),
+                }
+                items.extend(keyed.into_iter().map(|(_, item)| item));
+            }
+            for (i, item) in items.iter_mut().enumerate() {
+                path.push(i.to_string());
+                arrange(&mut item.value, path, rules);
+                path.pop();
+            }
+        }
+        Node::Scalar(_) => {}
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Number(_) => 2,
+            Value::String(_) => 3,
+            Value::Array(_) => 4,
+            Value::Object(_) => 5,
+        }
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => rank(a)
+            .cmp(&rank(b))
+            .then_with(|| a.to_string().cmp(&b.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern() {
+        let path = ["a", "b/c", "0"].map(String::from);
+        for (pattern, expected) in [
+            ("/a/b~1c/0", true),
+            ("/a/*/0", true),
+            ("/**/0", true),
+            ("/**", true),
+            ("/a/**/0", true),
+            ("/a/*", false),
+            ("", false),
+        ] {
+            let pattern: Pattern = pattern.parse().unwrap();
+            assert_eq!(pattern.matches(&path), expected, "{pattern:?}");
+        }
+        assert!("".parse::<Pattern>().unwrap().matches(&[]));
+        assert!("a".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn test_sort() {
+        let rules = r#"{"rules": [
+            {"path": "", "first": ["name", "version"]},
+            {"path": "/packageRules", "by": "groupName"},
+            {"path": "/**/extends", "array": "keep"}
+        ]}"#;
+        let mut rules: Rules = serde_json::from_str(rules).unwrap();
+        rules.sort_arrays = true;
+
+        let input = br#"{
+    "version": "1.0.0",
+    // inherited presets, in order
+    "extends": ["z", "a"],
+    "labels": ["deps", "bot"],
+    "packageRules": [
+        {"groupName": "rust", "matchManagers": ["cargo"]},
+        {"matchPackageNames": ["x"]}, // pinned
+        {"groupName": "node", "extends": ["y", "b"]}
+    ],
+    "name": "renovate"
+}
+"#;
+        let output = sort(Path::new("renovate.json"), input, &rules, None).unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+    "name": "renovate",
+    "version": "1.0.0",
+    // inherited presets, in order
+    "extends": ["z", "a"],
+    "labels": ["bot", "deps"],
+    "packageRules": [
+        {
+            "extends": ["y", "b"],
+            "groupName": "node"
+        },
+        {
+            "groupName": "rust",
+            "matchManagers": ["cargo"]
+        },
+        {
+            "matchPackageNames": ["x"]
+        } // pinned
+    ]
+}
+"#
+        );
+        assert_eq!(
+            sort(Path::new("renovate.json"), &output, &rules, None).unwrap(),
+            output
+        );
+    }
+}
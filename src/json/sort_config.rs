@@ -1,35 +1,359 @@
+use super::duplicate::Keep;
+use super::jsonc::{self, Item, Node};
+use super::schema::Schema;
+use super::vscode;
+use crate::batch;
+use crate::diagnostic::Diagnostic;
 use anyhow::{anyhow, Error, Result};
 use serde::{de, ser, Serialize};
-use serde_json::{ser::PrettyFormatter, Serializer, Value};
+use serde_json::{ser::PrettyFormatter, Map, Serializer, Value};
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
-use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str;
+
+pub const PATTERNS: &[&str] = &[
+    ".vscode/settings.json",
+    ".vscode/extensions.json",
+    ".vscode/keybindings.json",
+    ".vscode/launch.json",
+    ".vscode/tasks.json",
+    "*.code-workspace",
+];
+
+const INDENT: &str = "    ";
+
+// The sections of a workspace file, in the order VS Code writes them.
+const SECTIONS: &[&str] = &["folders", "settings", "launch", "tasks", "extensions"];
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
-    path: PathBuf,
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[arg(long, value_enum, value_name = "KEEP")]
+    allow_duplicates: Option<Keep>,
+
+    #[arg(long, value_enum)]
+    group: Option<Group>,
+
+    #[arg(long)]
+    merge_languages: bool,
+
+    /// Check settings against VS Code's schema and the extensions in --extensions-dir. Only some
+    /// of VS Code's own settings are bundled, so a misspelled one such as `editor.fontSzie` isn't
+    /// reported; unknown settings are only reported in an extension's namespace.
+    #[arg(long)]
+    validate: bool,
+
+    #[arg(long, value_name = "DIR", requires = "validate")]
+    extensions_dir: Vec<PathBuf>,
+
+    #[command(flatten)]
+    options: batch::Options,
 }
 
 pub fn main(args: Args) -> Result<()> {
-    let f = File::open(&args.path)?;
-    let config: BTreeMap<Key, Value> = serde_json::from_reader(f)?;
+    let schema = match args.validate {
+        true => Some(Schema::load(&args.extensions_dir)?),
+        false => None,
+    };
+    batch::run(&args.paths, &args.options, PATTERNS, |path, input| {
+        if let Some(schema) = &schema {
+            schema.validate(path, input)?;
+        }
+        let (output, warnings) = sort(
+            path,
+            input,
+            args.allow_duplicates,
+            args.group,
+            args.merge_languages,
+        )?;
+        batch::report_warnings(path, &warnings);
+        Ok(output)
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Group {
+    Blank,
+    Header,
+}
+
+pub fn sort(
+    path: &Path,
+    input: &[u8],
+    keep: Option<Keep>,
+    group: Option<Group>,
+    merge: bool,
+) -> Result<(Vec<u8>, Vec<String>)> {
+    let text = str::from_utf8(input)?;
+    let mut output = Vec::new();
+    let mut warnings = Vec::new();
+    match path.file_name().and_then(|name| name.to_str()) {
+        Some(name @ ("extensions.json" | "keybindings.json" | "launch.json" | "tasks.json")) => {
+            let (mut document, _): (_, Value) = jsonc::parse(path, text, keep)?;
+            document.root.sort_keys();
+            document.root.expand();
+            match name {
+                "extensions.json" => vscode::sort_extensions(&mut document.root),
+                "keybindings.json" => vscode::sort_keybindings(&mut document.root),
+                "launch.json" => vscode::sort_launch(&mut document.root),
+                _ => vscode::sort_tasks(&mut document.root),
+            }
+            output.extend(document.write(INDENT).into_bytes());
+        }
+        Some(name) if name.ends_with(".code-workspace") => {
+            let (mut document, _): (_, Map<String, Value>) = jsonc::parse(path, text, keep)?;
+            sort_workspace(path, text, &mut document.root, group, merge, &mut warnings)?;
+            output.extend(document.write(INDENT).into_bytes());
+        }
+        _ => {
+            let (mut document, _): (_, Entries) = jsonc::parse(path, text, keep)?;
+            sort_settings(path, text, &mut document.root, group, merge, &mut warnings)?;
+            output.extend(document.write(INDENT).into_bytes());
+        }
+    }
+    Ok((output, warnings))
+}
 
-    let mut f = File::create(&args.path)?;
-    let mut s = Serializer::with_formatter(&f, PrettyFormatter::with_indent(b"    "));
-    config.serialize(&mut s)?;
-    f.write_all(b"\n")?;
+// Orders the sections of a workspace and sorts the settings in it like a settings.json file.
+fn sort_workspace(
+    path: &Path,
+    text: &str,
+    node: &mut Node,
+    group: Option<Group>,
+    merge: bool,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    node.order(SECTIONS);
+    let Node::Object { items, .. } = node else {
+        return Ok(());
+    };
 
+    for item in items {
+        let name = item.name();
+        if name.as_deref() == Some("settings") {
+            sort_settings(path, text, &mut item.value, group, merge, warnings)?;
+            continue;
+        }
+        item.value.sort_keys();
+        item.value.expand();
+        // The other sections are sorted like the files they stand in for.
+        match name.as_deref() {
+            Some("launch") => vscode::sort_launch(&mut item.value),
+            Some("tasks") => vscode::sort_tasks(&mut item.value),
+            Some("extensions") => vscode::sort_extensions(&mut item.value),
+            _ => {}
+        }
+    }
     Ok(())
 }
 
+// Sorts settings by `Key`, keeping the comments that go with each.
+fn sort_settings(
+    path: &Path,
+    text: &str,
+    node: &mut Node,
+    group: Option<Group>,
+    merge: bool,
+    warnings: &mut Vec<String>,
+) -> Result<()> {
+    let Node::Object { items, .. } = node else {
+        return Ok(());
+    };
+
+    let mut entries = Vec::new();
+    for mut item in items.drain(..) {
+        // Headings from an earlier run are written again below, where the groups now start.
+        if group == Some(Group::Header) {
+            item.comments.retain(|comment| !is_heading(comment));
+        }
+        item.value.sort_keys();
+        item.value.expand();
+        let key = item.name().unwrap_or_default().parse::<Key>();
+        let key = key.map_err(|e| Diagnostic::at(path, text, item.offset, &e.to_string()))?;
+        entries.push((key, item));
+    }
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut sorted: Vec<(Key, Item)> = Vec::new();
+    for (key, item) in entries {
+        match sorted.last_mut() {
+            Some((last, block)) if merge && *last == key => merge_block(block, item),
+            _ => sorted.push((key, item)),
+        }
+    }
+
+    if merge {
+        let blocks = sorted
+            .iter()
+            .map(|(key, item)| (key, names(&item.value)))
+            .collect::<Vec<_>>();
+        warn_shadowed(&blocks, warnings);
+    }
+
+    let mut previous = None;
+    for i in 0..sorted.len() {
+        // Blocks that are spelled differently but name the same languages keep their spelling
+        // unless they were merged, so that neither replaces the other.
+        let same = |j: usize| sorted.get(j).is_some_and(|(key, _)| *key == sorted[i].0);
+        let unique = !same(i + 1) && (i == 0 || !same(i - 1));
+        let (key, item) = &mut sorted[i];
+        if unique {
+            item.key = Some(serde_json::to_string(&key.to_string())?.into());
+        }
+
+        let name = key.group().to_string();
+        if previous.as_ref() != Some(&name) {
+            item.blank = group.is_some() && i > 0;
+            if group == Some(Group::Header) {
+                item.comments.insert(0, format!("// --- {name} ---").into());
+            }
+        }
+        previous = Some(name);
+    }
+
+    *items = sorted.into_iter().map(|(_, item)| item).collect();
+    Ok(())
+}
+
+fn is_heading(comment: &str) -> bool {
+    comment.starts_with("// --- ") && comment.ends_with(" ---")
+}
+
+// Later settings in a language block win over earlier ones, as they would in VS Code.
+fn merge_block<'a>(block: &mut Item<'a>, item: Item<'a>) {
+    match (&mut block.value, item.value) {
+        (
+            Node::Object { items, .. },
+            Node::Object {
+                items: settings, ..
+            },
+        ) => {
+            for setting in settings {
+                items.retain(|other| other.name() != setting.name());
+                items.push(setting);
+            }
+            items.sort_by_cached_key(Item::name);
+            block.comments.extend(item.comments);
+            block.trailing.extend(item.trailing);
+        }
+        (_, value) => *block = Item { value, ..item },
+    }
+}
+
+fn names(node: &Node) -> Vec<String> {
+    match node {
+        Node::Object { items, .. } => items.iter().filter_map(Item::name).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn pretty<T: Serialize>(value: &T) -> Result<String> {
+    let mut s = Serializer::with_formatter(Vec::new(), PrettyFormatter::with_indent(b"    "));
+    value.serialize(&mut s)?;
+    Ok(String::from_utf8(s.into_inner())?)
+}
+
+pub(super) fn write<W: Write>(config: &BTreeMap<Key, Value>, mut w: W) -> Result<()> {
+    writeln!(w, "{}", pretty(config)?)?;
+    Ok(())
+}
+
+// Settings in file order. Language blocks spelled differently may map to the same key, so they
+// can't go straight into a map without one silently replacing the other.
+#[derive(Debug)]
+pub(super) struct Entries(Vec<(Key, Value)>);
+
+impl Entries {
+    // Merges blocks for the same languages, the way `sort` does with `--merge-languages`.
+    pub(super) fn collect(self, warnings: &mut Vec<String>) -> BTreeMap<Key, Value> {
+        let mut config = BTreeMap::new();
+        for (key, value) in self.0 {
+            match (config.get_mut(&key), value) {
+                (Some(Value::Object(block)), Value::Object(settings)) => block.extend(settings),
+                (_, value) => {
+                    config.insert(key, value);
+                }
+            }
+        }
+        let blocks = config
+            .iter()
+            .map(|(key, value)| {
+                let names = value.as_object().into_iter().flat_map(|block| block.keys());
+                (key, names.cloned().collect())
+            })
+            .collect::<Vec<_>>();
+        warn_shadowed(&blocks, warnings);
+        config
+    }
+}
+
+// Takes each block with the names of the settings in it.
+fn warn_shadowed(blocks: &[(&Key, Vec<String>)], warnings: &mut Vec<String>) {
+    for (key, names) in blocks {
+        let Key::Lang(languages) = key else {
+            continue;
+        };
+        if languages.len() < 2 {
+            continue;
+        }
+
+        for name in names {
+            for language in languages {
+                let single = Key::Lang(BTreeSet::from([language.clone()]));
+                if blocks
+                    .iter()
+                    .any(|(key, names)| **key == single && names.contains(name))
+                {
+                    warnings.push(format!("`{name}` in {key} is shadowed by {single}"));
+                }
+            }
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Entries {
+    fn deserialize<D: de::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Entries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        d.deserialize_map(Visitor)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum Key {
+pub(super) enum Key {
     Config(String),
     Lang(BTreeSet<String>),
 }
 
+impl Key {
+    fn group(&self) -> &str {
+        match self {
+            Key::Config(key) => key.split_once('.').map_or(key, |(prefix, _)| prefix),
+            Key::Lang(_) => "languages",
+        }
+    }
+}
+
 impl std::str::FromStr for Key {
     type Err = Error;
 
@@ -73,21 +397,28 @@ impl<'de> de::Deserialize<'de> for Key {
     }
 }
 
-impl ser::Serialize for Key {
-    fn serialize<S: ser::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Key::Config(key) => s.serialize_str(key),
-            Key::Lang(set) => s.serialize_str(&format!(
+            Key::Config(key) => f.write_str(key),
+            Key::Lang(set) => write!(
+                f,
                 "[{}]",
                 set.iter()
                     .map(String::as_str)
                     .collect::<Vec<_>>()
                     .join("][")
-            )),
+            ),
         }
     }
 }
 
+impl ser::Serialize for Key {
+    fn serialize<S: ser::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&self.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +438,313 @@ mod tests {
         );
         assert_de_tokens_error::<Key>(&[Token::String("[hoge")], "unmatched opening bracket");
     }
+
+    #[test]
+    fn test_group() {
+        let input = br#"{"files.a": 1, "[rust]": {"b": [1]}, "editor.b": 2, "editor.a": {"c": 3}, "zoom": 4}"#;
+        let path = Path::new("settings.json");
+
+        let mut expected = Vec::new();
+        let config: BTreeMap<Key, Value> = serde_json::from_slice(input).unwrap();
+        let mut s =
+            Serializer::with_formatter(&mut expected, PrettyFormatter::with_indent(b"    "));
+        config.serialize(&mut s).unwrap();
+        expected.push(b'\n');
+        assert_eq!(sort(path, input, None, None, false).unwrap().0, expected);
+
+        let output = sort(path, input, None, Some(Group::Header), false)
+            .unwrap()
+            .0;
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+    // --- editor ---
+    "editor.a": {
+        "c": 3
+    },
+    "editor.b": 2,
+
+    // --- files ---
+    "files.a": 1,
+
+    // --- zoom ---
+    "zoom": 4,
+
+    // --- languages ---
+    "[rust]": {
+        "b": [
+            1
+        ]
+    }
+}
+"#
+        );
+        assert_eq!(
+            sort(path, &output, None, Some(Group::Header), false)
+                .unwrap()
+                .0,
+            output
+        );
+
+        let output = sort(path, &output, None, Some(Group::Blank), false)
+            .unwrap()
+            .0;
+        assert!(str::from_utf8(&output)
+            .unwrap()
+            .contains("\"editor.b\": 2,\n\n    // --- files ---\n    \"files.a\": 1,\n\n"));
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = br#"// Editor settings
+{
+    "zoom": 4, // big
+    // Rust formatting
+    "[rust]": {"editor.tabSize": 2},
+    /* wrap */
+    "editor.wordWrap": "on",
+    // end
+}
+"#;
+        let path = Path::new("settings.json");
+        let output = sort(path, input, None, Some(Group::Header), false)
+            .unwrap()
+            .0;
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"// Editor settings
+{
+    // --- editor ---
+    /* wrap */
+    "editor.wordWrap": "on",
+
+    // --- zoom ---
+    "zoom": 4, // big
+
+    // --- languages ---
+    // Rust formatting
+    "[rust]": {
+        "editor.tabSize": 2
+    }
+    // end
+}
+"#
+        );
+        assert_eq!(
+            sort(path, &output, None, Some(Group::Header), false)
+                .unwrap()
+                .0,
+            output
+        );
+    }
+
+    #[test]
+    fn test_workspace() {
+        let input = br#"{
+    // Shared settings for the whole workspace.
+    "extensions": {"recommendations": ["rust-lang.rust-analyzer"]},
+    "settings": {"[rust]": {"editor.formatOnSave": true}, "files.eol": "\n", "editor.tabSize": 4},
+    "remoteAuthority": "wsl+Ubuntu",
+    "folders": [{"path": "b"}, {"path": "a", "name": "A"}]
+}"#;
+        let path = Path::new("junk.code-workspace");
+        let output = sort(path, input, None, Some(Group::Blank), false)
+            .unwrap()
+            .0;
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+    "folders": [
+        {
+            "path": "b"
+        },
+        {
+            "name": "A",
+            "path": "a"
+        }
+    ],
+    "settings": {
+        "editor.tabSize": 4,
+
+        "files.eol": "\n",
+
+        "[rust]": {
+            "editor.formatOnSave": true
+        }
+    },
+    // Shared settings for the whole workspace.
+    "extensions": {
+        "recommendations": [
+            "rust-lang.rust-analyzer"
+        ]
+    },
+    "remoteAuthority": "wsl+Ubuntu"
+}
+"#
+        );
+        assert_eq!(
+            sort(path, &output, None, Some(Group::Blank), false)
+                .unwrap()
+                .0,
+            output
+        );
+    }
+
+    #[test]
+    fn test_workspace_sections() {
+        let launch = r#"{"configurations": [{"type": "lldb", "name": "B"}, {"name": "A"}], "version": "0.2.0"}"#;
+        let tasks =
+            r#"{"tasks": [{"command": "b", "label": "B"}, {"label": "A"}], "version": "2.0.0"}"#;
+        let extensions = r#"{"recommendations": ["b.b", "A.a", "a.a"]}"#;
+        let workspace =
+            format!(r#"{{"extensions": {extensions}, "launch": {launch}, "tasks": {tasks}}}"#);
+
+        let sort = |name: &str, input: &str| {
+            let (output, _) = sort(Path::new(name), input.as_bytes(), None, None, false).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        let workspace = sort("junk.code-workspace", &workspace);
+        for (name, file, input) in [
+            ("launch", "launch.json", launch),
+            ("tasks", "tasks.json", tasks),
+            ("extensions", "extensions.json", extensions),
+        ] {
+            // A section is the file indented one level further.
+            let section = sort(file, input).trim_end().replace('\n', "\n    ");
+            let section = format!("\"{name}\": {section}");
+            assert!(workspace.contains(&section), "{workspace}\n{section}");
+        }
+    }
+
+    #[test]
+    fn test_vscode() {
+        let sort = |name: &str, input: &str| {
+            let (output, _) = sort(Path::new(name), input.as_bytes(), None, None, false).unwrap();
+            serde_json::from_slice::<Value>(&output).unwrap()
+        };
+
+        let extensions =
+            r#"{"unwantedRecommendations": ["b.c"], "recommendations": ["z.z", "A.b", "a.b"]}"#;
+        assert_eq!(
+            sort("extensions.json", extensions),
+            serde_json::json!({"recommendations": ["A.b", "z.z"], "unwantedRecommendations": ["b.c"]})
+        );
+
+        let keybindings = r#"[
+            {"key": "ctrl+b", "command": "one"},
+            {"key": "ctrl+a", "command": "two", "when": "z"},
+            {"key": "ctrl+a", "command": "three"},
+            {"key": "ctrl+a", "command": "-three"},
+            {"key": "ctrl+a", "command": "four", "when": "a"}
+        ]"#;
+        let commands = sort("keybindings.json", keybindings)
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|binding| binding["command"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(commands, ["three", "-three", "four", "two", "one"]);
+
+        let launch = r#"{
+            // comment
+            "configurations": [
+                {"request": "launch", "program": "b", "name": "B", "type": "lldb"},
+                {"name": "A", "type": "node", "request": "attach"}
+            ],
+            "version": "0.2.0"
+        }"#;
+        let (output, _) = super::sort(
+            Path::new("launch.json"),
+            launch.as_bytes(),
+            None,
+            None,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+    "version": "0.2.0",
+    // comment
+    "configurations": [
+        {
+            "name": "A",
+            "type": "node",
+            "request": "attach"
+        },
+        {
+            "name": "B",
+            "type": "lldb",
+            "request": "launch",
+            "program": "b"
+        }
+    ]
+}
+"#
+        );
+
+        let tasks = r#"{"tasks": [{"label": "test", "command": "cargo"}, {"label": "build", "type": "shell"}]}"#;
+        assert_eq!(sort("tasks.json", tasks)["tasks"][0]["label"], "build");
+    }
+
+    #[test]
+    fn test_merge_languages() {
+        let input = br#"{
+            "[toml][rust]": {"editor.tabSize": 2},
+            "[rust]": {"editor.tabSize": 4},
+            "[rust][toml]": {"editor.formatOnSave": true, "editor.tabSize": 8}
+        }"#;
+        let path = Path::new("settings.json");
+
+        // Without merging, both spellings of the same block are kept.
+        let output = sort(path, input, None, None, false).unwrap().0;
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+    "[rust]": {
+        "editor.tabSize": 4
+    },
+    "[toml][rust]": {
+        "editor.tabSize": 2
+    },
+    "[rust][toml]": {
+        "editor.formatOnSave": true,
+        "editor.tabSize": 8
+    }
+}
+"#
+        );
+
+        let input = br#"{
+            "[toml][rust]": {"editor.tabSize": 2, "files.eol": "\n"},
+            "[rust][toml]": {"editor.formatOnSave": true, "editor.tabSize": 8}
+        }"#;
+        let output = sort(path, input, None, None, true).unwrap().0;
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+    "[rust][toml]": {
+        "editor.formatOnSave": true,
+        "editor.tabSize": 8,
+        "files.eol": "\n"
+    }
+}
+"#
+        );
+
+        let input = br#"{
+            "[rust]": {"editor.tabSize": 4},
+            "[toml]": {"files.eol": "\n"},
+            "[toml][rust]": {"editor.tabSize": 2},
+            "[rust][toml]": {"editor.formatOnSave": true, "files.eol": "\r\n"}
+        }"#;
+        let (_, warnings) = sort(path, input, None, None, true).unwrap();
+        assert_eq!(
+            warnings,
+            [
+                "`editor.tabSize` in [rust][toml] is shadowed by [rust]",
+                "`files.eol` in [rust][toml] is shadowed by [toml]",
+            ]
+        );
+    }
 }
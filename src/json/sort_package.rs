@@ -0,0 +1,368 @@
+use super::duplicate::Keep;
+use crate::batch;
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde::Deserialize;
+use serde_json::{ser::PrettyFormatter, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str;
+
+pub const PATTERNS: &[&str] = &["package.json"];
+
+// The order used by sort-package-json. Fields not listed here follow in alphabetical order.
+const FIELDS: &[&str] = &[
+    "$schema",
+    "name",
+    "displayName",
+    "version",
+    "stableVersion",
+    "private",
+    "description",
+    "categories",
+    "keywords",
+    "homepage",
+    "bugs",
+    "repository",
+    "funding",
+    "license",
+    "qna",
+    "author",
+    "maintainers",
+    "contributors",
+    "publisher",
+    "sideEffects",
+    "type",
+    "imports",
+    "exports",
+    "main",
+    "svelte",
+    "umd:main",
+    "jsdelivr",
+    "unpkg",
+    "module",
+    "source",
+    "jsnext:main",
+    "browser",
+    "react-native",
+    "types",
+    "typesVersions",
+    "typings",
+    "style",
+    "example",
+    "examplestyle",
+    "assets",
+    "bin",
+    "man",
+    "directories",
+    "files",
+    "workspaces",
+    "binary",
+    "scripts",
+    "betterScripts",
+    "l10n",
+    "contributes",
+    "activationEvents",
+    "husky",
+    "simple-git-hooks",
+    "pre-commit",
+    "commitlint",
+    "lint-staged",
+    "nano-staged",
+    "config",
+    "nodemonConfig",
+    "browserify",
+    "babel",
+    "browserslist",
+    "xo",
+    "prettier",
+    "eslintConfig",
+    "eslintIgnore",
+    "npmpackagejsonlint",
+    "release",
+    "remarkConfig",
+    "stylelint",
+    "ava",
+    "jest",
+    "jest-junit",
+    "jest-stare",
+    "mocha",
+    "nyc",
+    "c8",
+    "tap",
+    "oclif",
+    "resolutions",
+    "dependencies",
+    "devDependencies",
+    "dependenciesMeta",
+    "peerDependencies",
+    "peerDependenciesMeta",
+    "optionalDependencies",
+    "bundledDependencies",
+    "bundleDependencies",
+    "extensionPack",
+    "extensionDependencies",
+    "flat",
+    "packageManager",
+    "engines",
+    "engineStrict",
+    "volta",
+    "languageName",
+    "os",
+    "cpu",
+    "preferGlobal",
+    "publishConfig",
+    "icon",
+    "badges",
+    "galleryBanner",
+    "preview",
+    "markdown",
+    "pnpm",
+];
+
+// Fields keyed by package name, which are sorted like the dependencies.
+const SORTED: &[&str] = &[
+    "bundleDependencies",
+    "bundledDependencies",
+    "dependenciesMeta",
+    "overrides",
+    "peerDependenciesMeta",
+    "resolutions",
+];
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[arg(long, value_enum, value_name = "KEEP")]
+    allow_duplicates: Option<Keep>,
+
+    #[command(flatten)]
+    options: batch::Options,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    batch::run(&args.paths, &args.options, PATTERNS, |path, input| {
+        sort(path, input, args.allow_duplicates)
+    })
+}
+
+pub fn sort(path: &Path, input: &[u8], keep: Option<Keep>) -> Result<Vec<u8>> {
+    let text = str::from_utf8(input)?;
+    let mut package: Package = super::from_str(path, text, keep)?;
+    if let Some(scripts) = &mut package.scripts {
+        scripts.sort();
+    }
+    for field in SORTED {
+        if let Some(value) = package.other.get_mut(*field) {
+            value.sort();
+        }
+    }
+
+    // npm writes two spaces; keep whatever the file already uses.
+    let mut serializer = serde_json::Serializer::with_formatter(
+        Vec::new(),
+        PrettyFormatter::with_indent(super::indent_of(text).as_bytes()),
+    );
+    package.serialize(&mut serializer)?;
+
+    let mut output = serializer.into_inner();
+    output.push(b'\n');
+    Ok(output)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Package {
+    pub scripts: Option<Scripts>,
+    pub dependencies: Option<BTreeMap<String, String>>,
+    pub dev_dependencies: Option<BTreeMap<String, String>>,
+    pub peer_dependencies: Option<BTreeMap<String, String>>,
+    pub optional_dependencies: Option<BTreeMap<String, String>>,
+    #[serde(flatten)]
+    pub other: IndexMap<String, Ordered>,
+}
+
+// A JSON value that keeps keys in the order they were written, since fields such as `exports`
+// depend on it.
+#[derive(Debug, Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum Ordered {
+    Object(IndexMap<String, Ordered>),
+    Array(Vec<Ordered>),
+    Scalar(Value),
+}
+
+impl Ordered {
+    // Sorts the keys of an object, or the names in an array, but not what is nested in them.
+    fn sort(&mut self) {
+        match self {
+            Ordered::Object(map) => map.sort_keys(),
+            Ordered::Array(items) => items.sort_by_cached_key(|item| match item {
+                Ordered::Scalar(Value::String(name)) => Some(name.clone()),
+                _ => None,
+            }),
+            Ordered::Scalar(_) => {}
+        }
+    }
+}
+
+impl Serialize for Package {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        fn entry<M: SerializeMap, T: Serialize>(
+            map: &mut M,
+            key: &str,
+            value: &Option<T>,
+        ) -> Result<(), M::Error> {
+            match value {
+                Some(value) => map.serialize_entry(key, value),
+                None => Ok(()),
+            }
+        }
+
+        let mut map = s.serialize_map(None)?;
+        for &field in FIELDS {
+            match field {
+                "scripts" => entry(&mut map, field, &self.scripts)?,
+                "dependencies" => entry(&mut map, field, &self.dependencies)?,
+                "devDependencies" => entry(&mut map, field, &self.dev_dependencies)?,
+                "peerDependencies" => entry(&mut map, field, &self.peer_dependencies)?,
+                "optionalDependencies" => entry(&mut map, field, &self.optional_dependencies)?,
+                _ => entry(&mut map, field, &self.other.get(field))?,
+            }
+        }
+        let mut rest = self
+            .other
+            .iter()
+            .filter(|(key, _)| !FIELDS.contains(&key.as_str()))
+            .collect::<Vec<_>>();
+        rest.sort_by_key(|&(key, _)| key);
+        for (key, value) in rest {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Debug, Default, Deserialize, serde::Serialize)]
+#[serde(transparent)]
+pub struct Scripts(pub IndexMap<String, String>);
+
+impl Scripts {
+    // Alphabetical, except that `preX` and `postX` stay around `X`, where npm runs them.
+    pub fn sort(&mut self) {
+        let names = self.0.keys().cloned().collect::<HashSet<_>>();
+        let key = |name: &str| {
+            for (prefix, rank) in [("pre", 0), ("post", 2)] {
+                if let Some(base) = name.strip_prefix(prefix) {
+                    if names.contains(base) {
+                        return (base.to_string(), rank);
+                    }
+                }
+            }
+            (name.to_string(), 1)
+        };
+        self.0.sort_by(|a, _, b, _| key(a).cmp(&key(b)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let input = br#"{
+  "devDependencies": {"vitest": "^1.0.0", "eslint": "^9.0.0"},
+  "scripts": {
+    "test": "vitest",
+    "postbuild": "echo done",
+    "prettier": "prettier -w .",
+    "build": "tsc",
+    "prebuild": "rimraf dist",
+    "pretest": "eslint ."
+  },
+  "zzz": true,
+  "exports": {
+    ".": {"types": "./index.d.ts", "import": "./index.mjs", "default": "./index.js"},
+    "./package.json": "./package.json"
+  },
+  "version": "1.0.0",
+  "aaa": [{"b": 1, "a": 2}],
+  "prettier": {"semi": false},
+  "name": "example",
+  "description": null,
+  "keywords": "cli",
+  "overrides": {"foo": {"bar": "1.0.0", ".": "2.0.0"}, "baz": "3.0.0"},
+  "bundledDependencies": ["b", "a"]
+}"#;
+        let output = sort(Path::new("package.json"), input, None).unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+  "name": "example",
+  "version": "1.0.0",
+  "description": null,
+  "keywords": "cli",
+  "exports": {
+    ".": {
+      "types": "./index.d.ts",
+      "import": "./index.mjs",
+      "default": "./index.js"
+    },
+    "./package.json": "./package.json"
+  },
+  "scripts": {
+    "prebuild": "rimraf dist",
+    "build": "tsc",
+    "postbuild": "echo done",
+    "prettier": "prettier -w .",
+    "pretest": "eslint .",
+    "test": "vitest"
+  },
+  "prettier": {
+    "semi": false
+  },
+  "devDependencies": {
+    "eslint": "^9.0.0",
+    "vitest": "^1.0.0"
+  },
+  "bundledDependencies": [
+    "a",
+    "b"
+  ],
+  "aaa": [
+    {
+      "b": 1,
+      "a": 2
+    }
+  ],
+  "overrides": {
+    "baz": "3.0.0",
+    "foo": {
+      "bar": "1.0.0",
+      ".": "2.0.0"
+    }
+  },
+  "zzz": true
+}
+"#
+        );
+
+        let error = sort(
+            Path::new("package.json"),
+            br#"{"dependencies": {"a": 1}}"#,
+            None,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("in dependencies.a"), "{error}");
+
+        let error = sort(Path::new("package.json"), br#"{"a": 1,}"#, None).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "package.json:1:9: trailing comma\n  |\n1 | {\"a\": 1,}\n  |         ^"
+        );
+    }
+}
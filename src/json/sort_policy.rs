@@ -1,3 +1,5 @@
+use super::duplicate::Keep;
+use crate::batch;
 use crate::serde_helper::{invariable, string};
 use anyhow::{anyhow, Error, Result};
 use indexmap::IndexMap;
@@ -7,39 +9,47 @@ use serde_json::{ser::PrettyFormatter, Serializer};
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 use std::fmt;
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
-use std::str::FromStr;
+use std::path::{Path, PathBuf};
+use std::str::{self, FromStr};
+
+pub const PATTERNS: &[&str] = &["*.policy.json"];
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
     files: Vec<PathBuf>,
+
+    #[arg(long, value_enum, value_name = "KEEP")]
+    allow_duplicates: Option<Keep>,
+
+    #[command(flatten)]
+    options: batch::Options,
 }
 
 pub fn main(args: Args) -> Result<()> {
-    for file in &args.files {
-        let f = File::open(file)?;
-        let policy: Policy = serde_json::from_reader(f)?;
+    batch::run(&args.files, &args.options, PATTERNS, |path, input| {
+        sort(path, input, args.allow_duplicates)
+    })
+}
 
-        let f = File::create(file)?;
-        let mut serializer = Serializer::with_formatter(f, PrettyFormatter::with_indent(b"    "));
-        policy.serialize(&mut serializer)?;
+pub fn sort(path: &Path, input: &[u8], keep: Option<Keep>) -> Result<Vec<u8>> {
+    let policy: Policy = super::from_str(path, str::from_utf8(input)?, keep)?;
 
-        let mut f = serializer.into_inner();
-        f.write_all(b"\n")?;
-    }
+    let mut serializer =
+        Serializer::with_formatter(Vec::new(), PrettyFormatter::with_indent(b"    "));
+    policy.serialize(&mut serializer)?;
 
-    Ok(())
+    let mut output = serializer.into_inner();
+    output.push(b'\n');
+    Ok(output)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct Arn {
-    partition: String,
-    service: String,
-    region: String,
-    account: String,
-    resource: String,
+pub struct Arn {
+    pub partition: String,
+    pub service: String,
+    pub region: String,
+    pub account: String,
+    pub resource: String,
 }
 
 impl FromStr for Arn {
@@ -97,31 +107,31 @@ impl fmt::Display for Arn {
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 #[serde(rename_all = "PascalCase")]
-struct Policy {
+pub struct Policy {
     #[serde(skip_serializing_if = "Option::is_none")]
-    version: Option<String>,
+    pub version: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    id: Option<String>,
+    pub id: Option<String>,
 
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         deserialize_with = "invariable::deserialize"
     )]
-    statement: Vec<Statement>,
+    pub statement: Vec<Statement>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Statement {
-    sid: Option<String>,
-    effect: Effect,
-    principal: Option<Principal>,
-    not_principal: Option<Principal>,
-    action: BTreeSet<Action>,
-    not_action: BTreeSet<Action>,
-    resource: BTreeSet<Resource>,
-    not_resource: BTreeSet<Resource>,
-    condition: IndexMap<String, IndexMap<String, Vec<String>>>,
+pub struct Statement {
+    pub sid: Option<String>,
+    pub effect: Effect,
+    pub principal: Option<Principal>,
+    pub not_principal: Option<Principal>,
+    pub action: BTreeSet<Action>,
+    pub not_action: BTreeSet<Action>,
+    pub resource: BTreeSet<Resource>,
+    pub not_resource: BTreeSet<Resource>,
+    pub condition: IndexMap<String, IndexMap<String, Vec<String>>>,
 }
 
 impl PartialOrd for Statement {
@@ -437,7 +447,7 @@ impl ser::Serialize for Statement {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Effect {
+pub enum Effect {
     Allow,
     Deny,
 }
@@ -464,11 +474,11 @@ impl fmt::Display for Effect {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-struct Principal {
-    aws: BTreeSet<Arn>,
-    canonical_user: BTreeSet<String>,
-    federated: BTreeSet<String>,
-    service: BTreeSet<String>,
+pub struct Principal {
+    pub aws: BTreeSet<Arn>,
+    pub canonical_user: BTreeSet<String>,
+    pub federated: BTreeSet<String>,
+    pub service: BTreeSet<String>,
 }
 
 impl<'de> de::Deserialize<'de> for Principal {
@@ -583,7 +593,7 @@ impl ser::Serialize for Principal {
 
         if !self.aws.is_empty() {
             let aws = self.aws.iter().map(string::wrap).collect::<Vec<_>>();
-            map.serialize_entry("Aws", &invariable::wrap(&aws))?;
+            map.serialize_entry("AWS", &invariable::wrap(&aws))?;
         }
 
         if !self.canonical_user.is_empty() {
@@ -603,7 +613,7 @@ impl ser::Serialize for Principal {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-enum Action {
+pub enum Action {
     Any,
     Name(String, String),
 }
@@ -635,7 +645,7 @@ impl fmt::Display for Action {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
-enum Resource {
+pub enum Resource {
     Any,
     Arn(Arn),
 }
@@ -745,6 +755,21 @@ mod tests {
             "illegal ARN: `12345678901A`",
         );
     }
+
+    #[test]
+    fn test_serde_principal() {
+        let input = r#"{"Version":"2012-10-17","Statement":[{"Effect":"Allow","Principal":{"AWS":"123456789012"},"Action":"sts:AssumeRole"}]}"#;
+        let output = sort(Path::new("policy.json"), input.as_bytes(), None).unwrap();
+        let text = str::from_utf8(&output).unwrap();
+        assert!(
+            text.contains(r#""AWS": "arn:aws:iam::123456789012:root""#),
+            "{text}"
+        );
+        assert_eq!(
+            sort(Path::new("policy.json"), &output, None).unwrap(),
+            output
+        );
+    }
 }
 
 mod condition {
@@ -0,0 +1,346 @@
+use super::duplicate::Keep;
+use super::jsonc::{self, Item, Node};
+use crate::batch;
+use crate::diagnostic::Diagnostic;
+use anyhow::Result;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::str;
+
+pub const PATTERNS: &[&str] = &["tsconfig.json", "tsconfig.*.json", "jsconfig.json"];
+
+const FIELDS: &[&str] = &[
+    "$schema",
+    "extends",
+    "compilerOptions",
+    "files",
+    "include",
+    "exclude",
+    "references",
+    "watchOptions",
+    "typeAcquisition",
+];
+
+// The groups of the TSConfig reference, in the order `tsc --init` writes them.
+const GROUPS: &[(&str, &[&str])] = &[
+    (
+        "Projects",
+        &[
+            "composite",
+            "disableReferencedProjectLoad",
+            "disableSolutionSearching",
+            "disableSourceOfProjectReferenceRedirect",
+            "incremental",
+            "tsBuildInfoFile",
+        ],
+    ),
+    (
+        "Language and Environment",
+        &[
+            "emitDecoratorMetadata",
+            "experimentalDecorators",
+            "jsx",
+            "jsxFactory",
+            "jsxFragmentFactory",
+            "jsxImportSource",
+            "lib",
+            "libReplacement",
+            "moduleDetection",
+            "noLib",
+            "reactNamespace",
+            "target",
+            "useDefineForClassFields",
+        ],
+    ),
+    (
+        "Modules",
+        &[
+            "allowArbitraryExtensions",
+            "allowImportingTsExtensions",
+            "allowUmdGlobalAccess",
+            "baseUrl",
+            "customConditions",
+            "module",
+            "moduleResolution",
+            "moduleSuffixes",
+            "noResolve",
+            "noUncheckedSideEffectImports",
+            "paths",
+            "resolveJsonModule",
+            "resolvePackageJsonExports",
+            "resolvePackageJsonImports",
+            "rewriteRelativeImportExtensions",
+            "rootDir",
+            "rootDirs",
+            "typeRoots",
+            "types",
+        ],
+    ),
+    (
+        "JavaScript Support",
+        &["allowJs", "checkJs", "maxNodeModuleJsDepth"],
+    ),
+    (
+        "Emit",
+        &[
+            "declaration",
+            "declarationDir",
+            "declarationMap",
+            "downlevelIteration",
+            "emitBOM",
+            "emitDeclarationOnly",
+            "importHelpers",
+            "inlineSourceMap",
+            "inlineSources",
+            "mapRoot",
+            "newLine",
+            "noEmit",
+            "noEmitHelpers",
+            "noEmitOnError",
+            "outDir",
+            "outFile",
+            "preserveConstEnums",
+            "removeComments",
+            "sourceMap",
+            "sourceRoot",
+            "stripInternal",
+        ],
+    ),
+    (
+        "Interop Constraints",
+        &[
+            "allowSyntheticDefaultImports",
+            "erasableSyntaxOnly",
+            "esModuleInterop",
+            "forceConsistentCasingInFileNames",
+            "isolatedDeclarations",
+            "isolatedModules",
+            "preserveSymlinks",
+            "verbatimModuleSyntax",
+        ],
+    ),
+    (
+        "Type Checking",
+        &[
+            "allowUnreachableCode",
+            "allowUnusedLabels",
+            "alwaysStrict",
+            "exactOptionalPropertyTypes",
+            "noFallthroughCasesInSwitch",
+            "noImplicitAny",
+            "noImplicitOverride",
+            "noImplicitReturns",
+            "noImplicitThis",
+            "noPropertyAccessFromIndexSignature",
+            "noUncheckedIndexedAccess",
+            "noUnusedLocals",
+            "noUnusedParameters",
+            "strict",
+            "strictBindCallApply",
+            "strictBuiltinIteratorReturn",
+            "strictFunctionTypes",
+            "strictNullChecks",
+            "strictPropertyInitialization",
+            "useUnknownInCatchVariables",
+        ],
+    ),
+    ("Completeness", &["skipDefaultLibCheck", "skipLibCheck"]),
+    (
+        "Compiler Diagnostics",
+        &[
+            "diagnostics",
+            "explainFiles",
+            "extendedDiagnostics",
+            "generateCpuProfile",
+            "generateTrace",
+            "listEmittedFiles",
+            "listFiles",
+            "noCheck",
+            "traceResolution",
+        ],
+    ),
+    ("Editor Support", &["disableSizeLimit", "plugins"]),
+    (
+        "Output Formatting",
+        &["noErrorTruncation", "preserveWatchOutput", "pretty"],
+    ),
+    (
+        "Backwards Compatibility",
+        &[
+            "charset",
+            "importsNotUsedAsValues",
+            "keyofStringsOnly",
+            "noImplicitUseStrict",
+            "noStrictGenericChecks",
+            "out",
+            "preserveValueImports",
+            "suppressExcessPropertyErrors",
+            "suppressImplicitAnyIndexErrors",
+        ],
+    ),
+];
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[arg(long, value_enum, value_name = "KEEP")]
+    allow_duplicates: Option<Keep>,
+
+    #[command(flatten)]
+    options: batch::Options,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    batch::run(&args.paths, &args.options, PATTERNS, |path, input| {
+        sort(path, input, args.allow_duplicates)
+    })
+}
+
+pub fn sort(path: &Path, input: &[u8], keep: Option<Keep>) -> Result<Vec<u8>> {
+    let text = str::from_utf8(input)?;
+    let (mut document, _): (_, Value) = jsonc::parse(path, text, keep)?;
+
+    if let Node::Object { items, .. } = &mut document.root {
+        items.sort_by_key(|item| rank(&[FIELDS], item));
+        for item in items {
+            match (item.name().as_deref(), &mut item.value) {
+                (Some("compilerOptions"), Node::Object { items, .. }) => {
+                    // The section headings `tsc --init` writes no longer fit once options move
+                    // between sections.
+                    for item in items.iter_mut() {
+                        item.comments.retain(|comment| !is_heading(comment));
+                    }
+                    let groups = GROUPS.iter().map(|&(_, group)| group).collect::<Vec<_>>();
+                    items.sort_by_key(|item| rank(&groups, item));
+                }
+                (Some("files" | "include" | "exclude"), Node::Array { items, .. }) => {
+                    jsonc::dedup(items, |item| item.value.scalar());
+                }
+                (Some(name @ ("files" | "include" | "exclude")), _) => {
+                    let message = format!("expected an array for `{name}`");
+                    return Err(Diagnostic::at(path, text, item.offset, &message).into());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(document.write(super::indent_of(text)).into_bytes())
+}
+
+fn is_heading(comment: &str) -> bool {
+    let text = comment
+        .strip_prefix("/*")
+        .and_then(|c| c.strip_suffix("*/"))
+        .or_else(|| comment.strip_prefix("//"))
+        .unwrap_or_default()
+        .trim();
+    GROUPS.iter().any(|&(name, _)| name == text)
+}
+
+fn rank(groups: &[&[&str]], item: &Item) -> (usize, usize, String) {
+    let name = item.name().unwrap_or_default();
+    groups
+        .iter()
+        .enumerate()
+        .find_map(|(i, group)| Some((i, group.iter().position(|field| *field == name)?)))
+        .map_or((usize::MAX, 0, name.clone()), |(i, j)| (i, j, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort() {
+        let input = r#"// Base configuration
+{
+  "include": ["src", "test", "src"],
+  "compilerOptions": {
+    /* Type Checking */
+    "strict": true, // everything
+    "skipLibCheck": true,
+    /* Language and Environment */
+    "target": "es2022",
+    "lib": ["es2022", "dom"],
+    "composite": true,
+    "paths": {
+      "@/*": ["src/*"],
+    },
+  },
+  "extends": "./tsconfig.base.json",
+  "exclude": [
+    "dist",
+    // generated
+    "dist"
+  ]
+}
+"#;
+        let output = sort(Path::new("tsconfig.json"), input.as_bytes(), None).unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"// Base configuration
+{
+  "extends": "./tsconfig.base.json",
+  "compilerOptions": {
+    "composite": true,
+    "lib": ["es2022", "dom"],
+    "target": "es2022",
+    "paths": {
+      "@/*": ["src/*"]
+    },
+    "strict": true, // everything
+    "skipLibCheck": true
+  },
+  "include": ["src", "test"],
+  "exclude": [
+    // generated
+    "dist"
+  ]
+}
+"#
+        );
+        assert_eq!(
+            sort(Path::new("tsconfig.json"), &output, None).unwrap(),
+            output
+        );
+
+        let error = sort(Path::new("tsconfig.json"), br#"{"include": "src"}"#, None).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("tsconfig.json:1:2: expected an array for `include`"));
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let path = Path::new("tsconfig.json");
+        let input = br#"{
+  "compilerOptions": {
+    "strict": false,
+    // stricter
+    "strict": true
+  }
+}
+"#;
+        assert!(sort(path, input, None).is_err());
+
+        let output = sort(path, input, Some(Keep::Last)).unwrap();
+        assert_eq!(
+            str::from_utf8(&output).unwrap(),
+            r#"{
+  "compilerOptions": {
+    // stricter
+    "strict": true
+  }
+}
+"#
+        );
+
+        let output = sort(path, input, Some(Keep::First)).unwrap();
+        assert!(str::from_utf8(&output)
+            .unwrap()
+            .contains(r#""strict": false"#));
+    }
+}
@@ -0,0 +1,61 @@
+use super::jsonc::{self, Item, Node};
+
+// Sorts the recommendations of an extensions.json file.
+pub fn sort_extensions(root: &mut Node) {
+    root.order(&["recommendations", "unwantedRecommendations"]);
+    for name in ["recommendations", "unwantedRecommendations"] {
+        if let Some(Node::Array { items, .. }) = root.get_mut(name) {
+            // Extension IDs are case-insensitive.
+            let id = |item: &Item| Some(item.value.scalar()?.as_str()?.to_lowercase());
+            items.sort_by_cached_key(id);
+            jsonc::dedup(items, id);
+        }
+    }
+}
+
+// Sorts the bindings of a keybindings.json file by key, then by context.
+pub fn sort_keybindings(root: &mut Node) {
+    let Node::Array { items, .. } = root else {
+        return;
+    };
+    // Later bindings win over earlier ones for the same key and context, so the sort has to be
+    // stable.
+    items.sort_by_cached_key(|item| (text(&item.value, "key"), text(&item.value, "when")));
+    for item in items {
+        item.value.order(&["key", "command", "when", "args"]);
+    }
+}
+
+// Sorts the configurations and compounds of a launch.json file by name, and its inputs by id.
+pub fn sort_launch(root: &mut Node) {
+    root.order(&["version", "configurations", "compounds", "inputs"]);
+    sort_array(root, "configurations", "name", &["name", "type", "request"]);
+    sort_array(root, "compounds", "name", &["name", "configurations"]);
+    sort_array(root, "inputs", "id", &["id", "type"]);
+}
+
+// Sorts the tasks of a tasks.json file by label, and its inputs by id.
+pub fn sort_tasks(root: &mut Node) {
+    root.order(&["version", "tasks", "inputs"]);
+    sort_array(
+        root,
+        "tasks",
+        "label",
+        &["label", "type", "command", "args"],
+    );
+    sort_array(root, "inputs", "id", &["id", "type"]);
+}
+
+// Sorts the objects in the array `name` by their `by` member and orders the keys in each.
+fn sort_array(node: &mut Node, name: &str, by: &str, fields: &[&str]) {
+    if let Some(Node::Array { items, .. }) = node.get_mut(name) {
+        items.sort_by_cached_key(|item| text(&item.value, by));
+        for item in items {
+            item.value.order(fields);
+        }
+    }
+}
+
+fn text(node: &Node, name: &str) -> Option<String> {
+    Some(node.get(name)?.scalar()?.as_str()?.to_string())
+}
@@ -0,0 +1,168 @@
+//! Sorters for Maven POMs, Maven settings and toolchains, IAM policies and VS Code settings.
+//!
+//! The `junk` binary wraps these with file handling; the functions here work on text in memory.
+
+mod batch;
+mod diagnostic;
+mod git;
+mod hook;
+mod json;
+mod lsp;
+mod serde_helper;
+mod xml;
+
+use anyhow::Result;
+use clap::Parser;
+use std::io::Read;
+use std::path::Path;
+use xml::document::Document;
+
+/// The IAM policy document model.
+pub mod policy {
+    pub use crate::json::sort_policy::{
+        Action, Arn, Effect, Policy, Principal, Resource, Statement,
+    };
+}
+
+/// The Maven POM model.
+pub mod maven {
+    pub use crate::xml::maven::*;
+}
+
+/// The Maven settings.xml model.
+pub mod settings {
+    pub use crate::xml::settings::*;
+}
+
+/// The Maven toolchains.xml model.
+pub mod toolchains {
+    pub use crate::xml::toolchains::*;
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(about, version)]
+enum Args {
+    #[command(subcommand)]
+    Hook(hook::Args),
+
+    #[command(subcommand)]
+    Json(json::Args),
+
+    Lsp,
+
+    #[command(subcommand)]
+    Xml(xml::Args),
+}
+
+#[doc(hidden)]
+pub fn run() -> Result<()> {
+    let args = Args::parse();
+    match args {
+        Args::Hook(args) => hook::main(args),
+        Args::Json(args) => json::main(args),
+        Args::Lsp => lsp::main(),
+        Args::Xml(args) => xml::main(args),
+    }
+}
+
+const INPUT: &str = "<input>";
+
+/// A sorted document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sorted {
+    /// The sorted text.
+    pub text: String,
+
+    /// Things worth knowing that did not stop the document from being sorted, such as
+    /// unrecognized elements that were kept as they were.
+    pub warnings: Vec<String>,
+}
+
+/// Sorts the statements of an IAM policy document.
+pub fn sort_policy_str(input: &str) -> Result<String> {
+    let output = json::sort_policy::sort(Path::new(INPUT), input.as_bytes(), None)?;
+    Ok(String::from_utf8(output)?)
+}
+
+/// Sorts the keys of a VS Code settings.json document, keeping its comments.
+pub fn sort_config_str(input: &str) -> Result<Sorted> {
+    let (output, warnings) =
+        json::sort_config::sort(Path::new(INPUT), input.as_bytes(), None, None, false)?;
+    Ok(Sorted {
+        text: String::from_utf8(output)?,
+        warnings,
+    })
+}
+
+/// Sorts the elements of a Maven POM.
+///
+/// The input may be in any encoding its XML declaration names; the returned text is decoded.
+pub fn sort_pom_reader<R: Read>(reader: R) -> Result<Sorted> {
+    sort_xml(reader, xml::sort_pom::sort)
+}
+
+/// Sorts the elements of a Maven settings.xml file, see [`sort_pom_reader`].
+pub fn sort_settings_reader<R: Read>(reader: R) -> Result<Sorted> {
+    sort_xml(reader, xml::sort_settings::sort::<settings::Settings>)
+}
+
+/// Sorts the elements of a Maven toolchains.xml file, see [`sort_pom_reader`].
+pub fn sort_toolchains_reader<R: Read>(reader: R) -> Result<Sorted> {
+    sort_xml(reader, xml::sort_settings::sort::<toolchains::Toolchains>)
+}
+
+fn sort_xml<R, F>(mut reader: R, sort: F) -> Result<Sorted>
+where
+    R: Read,
+    F: Fn(&Path, &[u8]) -> Result<(Vec<u8>, Vec<String>)>,
+{
+    let mut input = Vec::new();
+    reader.read_to_end(&mut input)?;
+    let (output, warnings) = sort(Path::new(INPUT), &input)?;
+    let (_, text) = Document::decode(&output)?;
+    Ok(Sorted { text, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_policy_str() {
+        let input = r#"{"Statement":[{"Effect":"Allow","Action":["s3:PutObject","s3:GetObject"],"Resource":"*"}],"Version":"2012-10-17"}"#;
+        assert_eq!(
+            sort_policy_str(input).unwrap(),
+            r#"{
+    "Version": "2012-10-17",
+    "Statement": [
+        {
+            "Effect": "Allow",
+            "Action": [
+                "s3:GetObject",
+                "s3:PutObject"
+            ],
+            "Resource": "*"
+        }
+    ]
+}
+"#
+        );
+        assert!(sort_policy_str("{").is_err());
+    }
+
+    #[test]
+    fn test_sort_pom_reader() {
+        let input = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<project><name>Jos\xE9</name><modelVersion>4.0.0</modelVersion></project>";
+        let Sorted { text: output, .. } = sort_pom_reader(&input[..]).unwrap();
+        assert!(output.starts_with("<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<project"));
+        assert!(
+            output.find("<modelVersion>").unwrap() < output.find("<name>Jos\u{E9}</name>").unwrap()
+        );
+
+        let input = r#"<project xmlns="http://maven.apache.org/POM/4.0.0" xmlns:v="urn:v"><v:vendor/></project>"#;
+        assert_eq!(
+            sort_pom_reader(input.as_bytes()).unwrap().warnings,
+            ["kept unrecognized elements as-is: project/v:vendor"]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use crate::batch;
+use crate::diagnostic::{Diagnostic, Diagnostics};
+use crate::json::{sort_config, sort_package, sort_policy, sort_tsconfig};
+use crate::xml::document::Document;
+use crate::xml::settings::Settings;
+use crate::xml::toolchains::Toolchains;
+use crate::xml::{sort_pom, sort_settings};
+use anyhow::Result;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as LspNotification, PublishDiagnostics,
+};
+use lsp_types::request::{Formatting, Request as LspRequest};
+use lsp_types::{
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DocumentFormattingParams, OneOf, Position, PublishDiagnosticsParams,
+    Range, ServerCapabilities, TextDocumentSyncKind, TextEdit, Uri,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+type Sort = fn(&Path, &str) -> Result<Vec<u8>>;
+
+const SORTERS: &[(&[&str], Sort)] = &[
+    (sort_config::PATTERNS, |path, text| {
+        let (output, warnings) = sort_config::sort(path, text.as_bytes(), None, None, false)?;
+        batch::report_warnings(path, &warnings);
+        Ok(output)
+    }),
+    (sort_package::PATTERNS, |path, text| {
+        sort_package::sort(path, text.as_bytes(), None)
+    }),
+    (sort_policy::PATTERNS, |path, text| {
+        sort_policy::sort(path, text.as_bytes(), None)
+    }),
+    (sort_tsconfig::PATTERNS, |path, text| {
+        sort_tsconfig::sort(path, text.as_bytes(), None)
+    }),
+    (sort_pom::PATTERNS, |path, text| {
+        let (output, warnings) = sort_pom::sort_text(path, &Document::from_text(text)?, text)?;
+        batch::report_warnings(path, &warnings);
+        Ok(output)
+    }),
+    (sort_settings::SETTINGS, |path, text| {
+        let document = Document::from_text(text)?;
+        let (output, warnings) = sort_settings::sort_text::<Settings>(path, &document, text)?;
+        batch::report_warnings(path, &warnings);
+        Ok(output)
+    }),
+    (sort_settings::TOOLCHAINS, |path, text| {
+        let document = Document::from_text(text)?;
+        let (output, warnings) = sort_settings::sort_text::<Toolchains>(path, &document, text)?;
+        batch::report_warnings(path, &warnings);
+        Ok(output)
+    }),
+];
+
+pub fn main() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    serve(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+fn serve(connection: Connection) -> Result<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncKind::FULL.into()),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let mut documents = BTreeMap::new();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    break;
+                }
+                connection
+                    .sender
+                    .send(respond(&documents, request).into())?;
+            }
+            Message::Notification(notification) => {
+                let method = notification.method.clone();
+                let uri = match update(&mut documents, notification) {
+                    Ok(Some(uri)) => uri,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        eprintln!("error: {method}: {e:#}");
+                        continue;
+                    }
+                };
+
+                let diagnostics = match documents.get(uri.as_str()) {
+                    Some(text) => diagnostics(&uri, text),
+                    None => Vec::new(),
+                };
+                let params = PublishDiagnosticsParams {
+                    uri,
+                    diagnostics,
+                    version: None,
+                };
+                let notification = Notification::new(PublishDiagnostics::METHOD.into(), params);
+                connection.sender.send(notification.into())?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+fn update(
+    documents: &mut BTreeMap<String, String>,
+    notification: Notification,
+) -> Result<Option<Uri>> {
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+            let document = params.text_document;
+            documents.insert(document.uri.to_string(), document.text);
+            Ok(Some(document.uri))
+        }
+        DidChangeTextDocument::METHOD => {
+            let mut params: DidChangeTextDocumentParams =
+                serde_json::from_value(notification.params)?;
+            let uri = params.text_document.uri;
+            if let Some(change) = params.content_changes.pop() {
+                documents.insert(uri.to_string(), change.text);
+            }
+            Ok(Some(uri))
+        }
+        DidCloseTextDocument::METHOD => {
+            let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+            documents.remove(params.text_document.uri.as_str());
+            Ok(Some(params.text_document.uri))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn respond(documents: &BTreeMap<String, String>, request: Request) -> Response {
+    if request.method != Formatting::METHOD {
+        let message = format!("unsupported method: {}", request.method);
+        return Response::new_err(request.id, ErrorCode::MethodNotFound as i32, message);
+    }
+
+    let params: DocumentFormattingParams = match serde_json::from_value(request.params) {
+        Ok(params) => params,
+        Err(e) => {
+            return Response::new_err(request.id, ErrorCode::InvalidParams as i32, e.to_string())
+        }
+    };
+
+    let uri = params.text_document.uri;
+    match documents.get(uri.as_str()).map(|text| format(&uri, text)) {
+        Some(Ok(edits)) => Response::new_ok(request.id, edits),
+        Some(Err(e)) => {
+            let message = e.to_string().lines().next().unwrap_or_default().to_string();
+            Response::new_err(request.id, ErrorCode::RequestFailed as i32, message)
+        }
+        None => Response::new_ok(request.id, None::<Vec<TextEdit>>),
+    }
+}
+
+fn sorter(uri: &Uri) -> Option<(PathBuf, Sort)> {
+    let path = PathBuf::from(uri.path().as_estr().decode().into_string_lossy().as_ref());
+    SORTERS
+        .iter()
+        .find(|(patterns, _)| batch::glob_set(patterns).is_ok_and(|set| set.is_match(&path)))
+        .map(|&(_, sort)| (path, sort))
+}
+
+fn format(uri: &Uri, text: &str) -> Result<Option<Vec<TextEdit>>> {
+    let Some((path, sort)) = sorter(uri) else {
+        return Ok(None);
+    };
+
+    let output = String::from_utf8(sort(&path, text)?)?;
+    if output == text {
+        return Ok(Some(Vec::new()));
+    }
+
+    let last = text.rsplit('\n').next().unwrap_or_default();
+    let end = Position::new(
+        text.matches('\n').count() as u32,
+        last.encode_utf16().count() as u32,
+    );
+    Ok(Some(vec![TextEdit::new(
+        Range::new(Position::new(0, 0), end),
+        output,
+    )]))
+}
+
+fn diagnostics(uri: &Uri, text: &str) -> Vec<lsp_types::Diagnostic> {
+    let Some((path, sort)) = sorter(uri) else {
+        return Vec::new();
+    };
+    let Err(error) = sort(&path, text) else {
+        return Vec::new();
+    };
+
+    let diagnostics = match (
+        error.downcast_ref::<Diagnostic>(),
+        error.downcast_ref::<Diagnostics>(),
+    ) {
+        (Some(diagnostic), _) => vec![diagnostic],
+        (_, Some(diagnostics)) => diagnostics.0.iter().collect(),
+        _ => {
+            return vec![lsp_types::Diagnostic {
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("junk".to_string()),
+                message: format!("{error:#}"),
+                ..Default::default()
+            }]
+        }
+    };
+
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+            let character = diagnostic
+                .snippet
+                .chars()
+                .take(diagnostic.column - 1)
+                .map(char::len_utf16)
+                .sum::<usize>();
+            let position = Position::new(diagnostic.line as u32 - 1, character as u32);
+            let message = match &diagnostic.context {
+                Some(context) => format!("{}\nin {context}", diagnostic.message),
+                None => diagnostic.message.clone(),
+            };
+            lsp_types::Diagnostic {
+                range: Range::new(position, position),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("junk".to_string()),
+                message,
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::{
+        DidOpenTextDocumentParams, InitializeParams, InitializedParams, TextDocumentIdentifier,
+        TextDocumentItem,
+    };
+    use std::thread;
+
+    fn recv(client: &Connection) -> serde_json::Value {
+        match client.receiver.recv().unwrap() {
+            Message::Response(response) => response.result.unwrap(),
+            Message::Notification(notification) => notification.params,
+            Message::Request(request) => panic!("unexpected request: {request:?}"),
+        }
+    }
+
+    fn open(client: &Connection, uri: &Uri, text: &str) -> serde_json::Value {
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "json".into(), 1, text.into()),
+        };
+        let notification = Notification::new(DidOpenTextDocument::METHOD.into(), params);
+        client.sender.send(notification.into()).unwrap();
+        recv(client)["diagnostics"].clone()
+    }
+
+    #[test]
+    fn test_serve() {
+        let (server, client) = Connection::memory();
+        let server = thread::spawn(move || serve(server));
+
+        let request = Request::new(1.into(), "initialize".into(), InitializeParams::default());
+        client.sender.send(request.into()).unwrap();
+        assert_eq!(
+            recv(&client)["capabilities"]["documentFormattingProvider"],
+            true
+        );
+        let notification = Notification::new("initialized".into(), InitializedParams {});
+        client.sender.send(notification.into()).unwrap();
+
+        let uri = "file:///work/.vscode/settings.json".parse::<Uri>().unwrap();
+        assert_eq!(
+            open(&client, &uri, r#"{"b": 1, "a": 2}"#),
+            serde_json::json!([])
+        );
+
+        let params = DocumentFormattingParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            options: Default::default(),
+            work_done_progress_params: Default::default(),
+        };
+        let request = Request::new(2.into(), Formatting::METHOD.into(), params);
+        client.sender.send(request.into()).unwrap();
+        assert_eq!(
+            recv(&client),
+            serde_json::json!([{
+                "range": {
+                    "start": { "line": 0, "character": 0 },
+                    "end": { "line": 0, "character": 16 },
+                },
+                "newText": "{\n    \"a\": 2,\n    \"b\": 1\n}\n",
+            }])
+        );
+
+        let diagnostics = open(&client, &uri, "{\n  \"a\": \u{e9}\n}");
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+        assert_eq!(diagnostics[0]["range"]["start"]["character"], 7);
+        assert_eq!(diagnostics[0]["message"], "expected value\nin a");
+
+        let diagnostics = open(&client, &uri, "{\n  \"a\": 1,\n  \"a\": 2,\n  \"a\": 3\n}");
+        assert_eq!(diagnostics.as_array().unwrap().len(), 2);
+        assert_eq!(diagnostics[1]["range"]["start"]["line"], 3);
+
+        let uri = "file:///work/pom.xml".parse::<Uri>().unwrap();
+        let diagnostics = open(&client, &uri, "<project>\n  <a></b>\n</project>\n");
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+
+        // A malformed notification is logged and skipped.
+        let notification = Notification::new(DidOpenTextDocument::METHOD.into(), ());
+        client.sender.send(notification.into()).unwrap();
+
+        let uri = "file:///work/readme.json".parse::<Uri>().unwrap();
+        assert_eq!(open(&client, &uri, "oops"), serde_json::json!([]));
+
+        let request = Request::new(3.into(), "shutdown".into(), ());
+        client.sender.send(request.into()).unwrap();
+        recv(&client);
+        let notification = Notification::new("exit".into(), ());
+        client.sender.send(notification.into()).unwrap();
+        server.join().unwrap().unwrap();
+    }
+}
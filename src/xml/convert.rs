@@ -0,0 +1,154 @@
+use super::document::Document;
+use super::maven::Model;
+use super::sort_pom;
+use crate::diagnostic::Diagnostic;
+use anyhow::Result;
+use serde_yaml::Value;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    path: PathBuf,
+}
+
+pub fn pom_to_json(args: Args) -> Result<()> {
+    let value = read(&args)?;
+
+    serde_json::to_writer_pretty(io::stdout().lock(), &value)?;
+    println!();
+
+    Ok(())
+}
+
+pub fn pom_to_yaml(args: Args) -> Result<()> {
+    let value = read(&args)?;
+
+    serde_yaml::to_writer(io::stdout().lock(), &value)?;
+
+    Ok(())
+}
+
+pub fn json_to_pom(args: Args) -> Result<()> {
+    let text = fs::read_to_string(&args.path)?;
+    let pom: Model = crate::json::from_str(&args.path, &text, None)?;
+    sort_pom::write(&pom, &Document::default(), io::stdout().lock())
+}
+
+pub fn yaml_to_pom(args: Args) -> Result<()> {
+    let text = fs::read_to_string(&args.path)?;
+    let pom: Model =
+        serde_yaml::from_str(&text).map_err(|e| Diagnostic::yaml(&args.path, &text, &e))?;
+    sort_pom::write(&pom, &Document::default(), io::stdout().lock())
+}
+
+fn read(args: &Args) -> Result<Value> {
+    let (_, pom, unknown) = super::read::<Model>(&args.path)?;
+    super::warn_unknown(&args.path, &unknown);
+    to_value(&pom)
+}
+
+fn to_value(pom: &Model) -> Result<Value> {
+    let mut value = serde_yaml::to_value(pom)?;
+    prune(&mut value);
+    Ok(value)
+}
+
+fn prune(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(prune);
+        }
+        Value::Sequence(sequence) => sequence.iter_mut().for_each(prune),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::maven;
+
+    #[test]
+    fn test_round_trip() {
+        let pom: Model = yaserde::de::from_str(
+            r#"<project xmlns="http://maven.apache.org/POM/4.0.0" child.project.url.inherit.append.path="false">
+                <modelVersion>4.0.0</modelVersion>
+                <artifactId>a</artifactId>
+                <properties><java.version>17</java.version></properties>
+                <dependencies>
+                    <dependency><groupId>g</groupId><artifactId>b</artifactId><type>pom</type></dependency>
+                </dependencies>
+                <build><plugins><plugin>
+                    <artifactId>maven-compiler-plugin</artifactId>
+                    <configuration><release>17</release><compilerArgs><arg>-Xlint</arg></compilerArgs></configuration>
+                </plugin></plugins></build>
+            </project>"#,
+        )
+        .unwrap();
+
+        let value = to_value(&pom).unwrap();
+        assert_eq!(value["child.project.url.inherit.append.path"], "false");
+        assert_eq!(value["properties"]["java.version"], "17");
+        assert_eq!(value["dependencies"][0]["type"], "pom");
+        assert!(value.get("groupId").is_none());
+        assert!(serde_json::to_string(&value)
+            .unwrap()
+            .contains(r#"{"release":"17","compilerArgs":"#));
+
+        let yaml = serde_yaml::to_string(&value).unwrap();
+        let back: Model = serde_yaml::from_str(&yaml).unwrap();
+
+        let mut expected = Vec::new();
+        sort_pom::write(&pom, &Document::default(), &mut expected).unwrap();
+        let mut actual = Vec::new();
+        sort_pom::write(&back, &Document::default(), &mut actual).unwrap();
+        assert_eq!(
+            String::from_utf8(actual).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unknown() {
+        let (pom, unknown) = maven::collect_unknown(|| {
+            yaserde::de::from_str::<Model>(
+                r#"<project xmlns="http://maven.apache.org/POM/4.0.0" xmlns:v="urn:v">
+                    <artifactId>a</artifactId>
+                    <v:vendor id="1"><v:name>x</v:name></v:vendor>
+                    <dependencies><extra/></dependencies>
+                    <build><plugins><plugin><artifactId>p</artifactId><goals><goal>b</goal></goals></plugin></plugins></build>
+                </project>"#,
+            )
+        });
+        let value = to_value(&pom.unwrap()).unwrap();
+        assert_eq!(
+            value["unknown"][0],
+            r#"<v:vendor xmlns:v="urn:v" id="1"><v:name>x</v:name></v:vendor>"#
+        );
+        assert_eq!(
+            value["build"]["plugins"][0]["unknown"][0],
+            "<goals><goal>b</goal></goals>"
+        );
+
+        assert_eq!(
+            value["dependencies"],
+            serde_yaml::from_str::<Value>("{dependency: [], unknown: ['<extra />']}").unwrap()
+        );
+        assert_eq!(
+            unknown,
+            ["dependencies/extra", "plugin/goals", "project/v:vendor"]
+        );
+
+        let back: Model = serde_yaml::from_value(value).unwrap();
+        let mut xml = Vec::new();
+        sort_pom::write(&back, &Document::default(), &mut xml).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+        assert!(
+            xml.contains("<dependencies>\n    <extra />\n  </dependencies>"),
+            "{xml}"
+        );
+    }
+}
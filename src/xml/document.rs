@@ -0,0 +1,224 @@
+use anyhow::{anyhow, bail, Result};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Declaration {
+    pub version: String,
+    pub encoding: Option<String>,
+    pub standalone: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub declaration: Option<Declaration>,
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+impl Default for Document {
+    fn default() -> Self {
+        Self {
+            declaration: Some(Declaration {
+                version: "1.0".to_string(),
+                encoding: Some("UTF-8".to_string()),
+                standalone: None,
+            }),
+            encoding: UTF_8,
+            bom: false,
+        }
+    }
+}
+
+impl Document {
+    pub fn decode(input: &[u8]) -> Result<(Self, String)> {
+        let (encoding, bom) = match Encoding::for_bom(input) {
+            Some((encoding, len)) => (Some(encoding), len),
+            None => match input {
+                [0x3C, 0x00, 0x3F, 0x00, ..] => (Some(UTF_16LE), 0),
+                [0x00, 0x3C, 0x00, 0x3F, ..] => (Some(UTF_16BE), 0),
+                _ => (None, 0),
+            },
+        };
+        let input = &input[bom..];
+
+        let encoding = match encoding {
+            Some(encoding) => encoding,
+            None => {
+                let end = input
+                    .windows(2)
+                    .position(|w| w == b"?>")
+                    .map_or(0, |i| i + 2);
+                let prolog = String::from_utf8_lossy(&input[..end]);
+                match parse(&prolog)?.and_then(|d| d.encoding) {
+                    Some(label) => Encoding::for_label(label.as_bytes())
+                        .ok_or_else(|| anyhow!("unsupported encoding: {label}"))?,
+                    None => UTF_8,
+                }
+            }
+        };
+
+        let text = encoding
+            .decode_without_bom_handling_and_without_replacement(input)
+            .ok_or_else(|| anyhow!("malformed {} input", encoding.name()))?;
+
+        let document = Self {
+            declaration: parse(&text)?,
+            encoding,
+            bom: bom > 0,
+        };
+        Ok((document, text.into_owned()))
+    }
+
+    // Text that has already been decoded, e.g. by an editor, is written back as UTF-8 with its
+    // declaration left as it was.
+    pub fn from_text(text: &str) -> Result<Self> {
+        Ok(Self {
+            declaration: parse(text)?,
+            encoding: UTF_8,
+            bom: false,
+        })
+    }
+
+    pub fn encode(&self, body: &str) -> Vec<u8> {
+        let mut text = String::new();
+        if let Some(declaration) = &self.declaration {
+            text.push_str(&format!("<?xml version=\"{}\"", declaration.version));
+            if let Some(encoding) = &declaration.encoding {
+                text.push_str(&format!(" encoding=\"{encoding}\""));
+            }
+            if let Some(standalone) = &declaration.standalone {
+                text.push_str(&format!(" standalone=\"{standalone}\""));
+            }
+            text.push_str("?>\n");
+        }
+        text.push_str(body);
+
+        let mut output = Vec::new();
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            let le = self.encoding == UTF_16LE;
+            let units = self.bom.then_some('\u{FEFF}' as u16).into_iter();
+            for unit in units.chain(text.encode_utf16()) {
+                output.extend(if le {
+                    unit.to_le_bytes()
+                } else {
+                    unit.to_be_bytes()
+                });
+            }
+        } else {
+            if self.bom && self.encoding == UTF_8 {
+                output.extend(b"\xEF\xBB\xBF");
+            }
+            output.extend(self.encoding.encode(&text).0.iter());
+        }
+        output
+    }
+}
+
+fn parse(text: &str) -> Result<Option<Declaration>> {
+    let Some(rest) = text.strip_prefix("<?xml") else {
+        return Ok(None);
+    };
+    if !rest.starts_with(|c: char| c.is_ascii_whitespace()) {
+        return Ok(None);
+    }
+    let end = rest
+        .find("?>")
+        .ok_or_else(|| anyhow!("unterminated XML declaration"))?;
+
+    let mut declaration = Declaration {
+        version: String::new(),
+        encoding: None,
+        standalone: None,
+    };
+
+    let mut attributes = &rest[..end];
+    loop {
+        attributes = attributes.trim_start();
+        if attributes.is_empty() {
+            break;
+        }
+
+        let (name, value) = attributes
+            .split_once('=')
+            .ok_or_else(|| anyhow!("malformed XML declaration"))?;
+        let value = value.trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '"' | '\''))
+            .ok_or_else(|| anyhow!("malformed XML declaration"))?;
+        let (value, rest) = value[1..]
+            .split_once(quote)
+            .ok_or_else(|| anyhow!("malformed XML declaration"))?;
+        attributes = rest;
+
+        match name.trim() {
+            "version" => declaration.version = value.to_string(),
+            "encoding" => declaration.encoding = Some(value.to_string()),
+            "standalone" => declaration.standalone = Some(value.to_string()),
+            name => bail!("unexpected `{name}` in XML declaration"),
+        }
+    }
+
+    Ok(Some(declaration))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_declaration() {
+        let (document, text) = Document::decode(b"<project/>").unwrap();
+        assert_eq!(document.declaration, None);
+        assert_eq!(text, "<project/>");
+        assert_eq!(document.encode("<project/>\n"), b"<project/>\n");
+    }
+
+    #[test]
+    fn test_standalone() {
+        let input = b"<?xml version='1.1' standalone='yes'?>\n<project/>\n";
+        let (document, text) = Document::decode(input).unwrap();
+        assert_eq!(
+            document.declaration,
+            Some(Declaration {
+                version: "1.1".to_string(),
+                encoding: None,
+                standalone: Some("yes".to_string()),
+            })
+        );
+        assert_eq!(text.as_bytes(), input);
+        assert_eq!(
+            document.encode("<project/>\n"),
+            b"<?xml version=\"1.1\" standalone=\"yes\"?>\n<project/>\n"
+        );
+    }
+
+    #[test]
+    fn test_transcode() {
+        let input = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?><name>Jos\xE9</name>";
+        let (document, text) = Document::decode(input).unwrap();
+        assert!(text.ends_with("<name>Jos\u{E9}</name>"));
+        assert_eq!(
+            document.encode("<name>Jos\u{E9} \u{2603}</name>"),
+            b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<name>Jos\xE9 &#9731;</name>"
+        );
+    }
+
+    #[test]
+    fn test_utf16() {
+        let input = "\u{FEFF}<?xml version=\"1.0\" encoding=\"UTF-16\"?><a/>"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        let (document, text) = Document::decode(&input).unwrap();
+        assert_eq!(text, "<?xml version=\"1.0\" encoding=\"UTF-16\"?><a/>");
+        assert_eq!(
+            document.encode("<a/>"),
+            "\u{FEFF}<?xml version=\"1.0\" encoding=\"UTF-16\"?>\n<a/>"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes)
+                .collect::<Vec<_>>()
+        );
+    }
+}
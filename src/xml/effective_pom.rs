@@ -0,0 +1,1040 @@
+use super::document::Document;
+use super::maven::*;
+use anyhow::{anyhow, bail, Error, Result};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::hash::Hash;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use xml::reader::XmlEvent;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(long)]
+    repository: Option<PathBuf>,
+
+    path: PathBuf,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let repository = match args.repository {
+        Some(repository) => repository,
+        None => default_repository()?,
+    };
+
+    let mut pom = resolve(&args.path, &repository)?;
+    manage(&mut pom);
+    let properties = properties(&args.path, &pom);
+
+    let xml = yaserde::ser::serialize_with_writer(
+        &pom,
+        Vec::new(),
+        &yaserde::ser::Config {
+            perform_indent: true,
+            write_document_declaration: false,
+            indent_string: None,
+        },
+    )
+    .map_err(Error::msg)?;
+    let xml = super::map_events(&xml[..], |event| match event {
+        XmlEvent::Characters(s) => XmlEvent::Characters(interpolate(&s, &properties)),
+        XmlEvent::StartElement {
+            name,
+            mut attributes,
+            namespace,
+        } => {
+            for attribute in &mut attributes {
+                attribute.value = interpolate(&attribute.value, &properties);
+            }
+
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            }
+        }
+        event => event,
+    })?;
+
+    let mut xml = String::from_utf8(xml)?;
+    xml.push('\n');
+    io::stdout()
+        .lock()
+        .write_all(&Document::default().encode(&xml))?;
+
+    Ok(())
+}
+
+pub fn default_repository() -> Result<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".m2").join("repository"))
+        .ok_or_else(|| anyhow!("unable to locate the local repository"))
+}
+
+pub fn resolve(path: &Path, repository: &Path) -> Result<Model> {
+    resolve_imports(path, repository, &mut Vec::new())
+}
+
+fn resolve_imports(path: &Path, repository: &Path, imports: &mut Vec<PathBuf>) -> Result<Model> {
+    let pom = load(path)?;
+    let mut pom = effective(path, pom, repository, &mut Vec::new())?;
+    import(path, &mut pom, repository, imports)?;
+    Ok(pom)
+}
+
+fn load(path: &Path) -> Result<Model> {
+    let (_, pom, unknown) = super::read(path)?;
+    super::warn_unknown(path, &unknown);
+    Ok(pom)
+}
+
+fn effective(
+    path: &Path,
+    mut pom: Model,
+    repository: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> Result<Model> {
+    let canonical = fs::canonicalize(path)?;
+    if chain.contains(&canonical) {
+        bail!("cyclic parent reference at {}", path.display());
+    }
+    chain.push(canonical);
+
+    activate(&mut pom);
+
+    if let Some(parent) = &pom.parent {
+        let (parent_path, parent) = locate(path, parent, repository)?;
+        let parent = effective(&parent_path, parent, repository, chain)?;
+        pom = inherit(pom, parent);
+    }
+
+    Ok(pom)
+}
+
+fn locate(path: &Path, parent: &Parent, repository: &Path) -> Result<(PathBuf, Model)> {
+    let relative_path = parent.relative_path.as_deref().unwrap_or("../pom.xml");
+    if !relative_path.is_empty() {
+        let mut candidate = path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(relative_path);
+        if candidate.is_dir() {
+            candidate = candidate.join("pom.xml");
+        }
+
+        if candidate.is_file() {
+            let pom = load(&candidate)?;
+            let group_id = pom
+                .group_id
+                .as_ref()
+                .or_else(|| pom.parent.as_ref().and_then(|p| p.group_id.as_ref()));
+            if group_id == parent.group_id.as_ref() && pom.artifact_id == parent.artifact_id {
+                return Ok((candidate, pom));
+            }
+        }
+    }
+
+    let (Some(group_id), Some(artifact_id), Some(version)) =
+        (&parent.group_id, &parent.artifact_id, &parent.version)
+    else {
+        bail!("incomplete parent coordinates in {}", path.display());
+    };
+
+    let candidate = artifact(repository, group_id, artifact_id, version);
+    if !candidate.is_file() {
+        bail!("parent {group_id}:{artifact_id}:{version} not found");
+    }
+
+    let pom = load(&candidate)?;
+    Ok((candidate, pom))
+}
+
+fn artifact(repository: &Path, group_id: &str, artifact_id: &str, version: &str) -> PathBuf {
+    group_id
+        .split('.')
+        .fold(repository.to_path_buf(), |path, part| path.join(part))
+        .join(artifact_id)
+        .join(version)
+        .join(format!("{artifact_id}-{version}.pom"))
+}
+
+// Managed dependencies with `import` scope stand for everything the BOM they point to manages.
+// Entries declared directly win over imported ones, and earlier imports over later ones.
+fn import(
+    path: &Path,
+    pom: &mut Model,
+    repository: &Path,
+    imports: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let own = properties(path, pom);
+    let Some(dependencies) = pom
+        .dependency_management
+        .as_mut()
+        .and_then(|d| d.dependencies.as_mut())
+    else {
+        return Ok(());
+    };
+
+    let (boms, mut managed): (Vec<_>, Vec<_>) =
+        dependencies.dependencies.drain(..).partition(|d| {
+            d.scope.as_deref() == Some("import") && d.dependency_type.as_deref() == Some("pom")
+        });
+    for bom in boms {
+        let bom = interpolate_dependency(bom, &own);
+        let (Some(group_id), Some(artifact_id), Some(version)) =
+            (&bom.group_id, &bom.artifact_id, &bom.version)
+        else {
+            bail!(
+                "incomplete coordinates of an imported BOM in {}",
+                path.display()
+            );
+        };
+
+        let candidate = artifact(repository, group_id, artifact_id, version);
+        if !candidate.is_file() {
+            bail!("imported BOM {group_id}:{artifact_id}:{version} not found");
+        }
+        let canonical = fs::canonicalize(&candidate)?;
+        if imports.contains(&canonical) {
+            bail!("cyclic import of BOM {group_id}:{artifact_id}:{version}");
+        }
+
+        imports.push(canonical);
+        let bom = resolve_imports(&candidate, repository, imports)?;
+        imports.pop();
+
+        let bom_properties = properties(&candidate, &bom);
+        let imported = bom
+            .dependency_management
+            .and_then(|d| d.dependencies)
+            .map(|d| d.dependencies)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|d| interpolate_dependency(d, &bom_properties));
+        managed = merge_by(managed, imported.collect(), dependency_key, |d, _| d);
+    }
+    dependencies.dependencies = managed;
+    Ok(())
+}
+
+fn interpolate_dependency(
+    dependency: Dependency,
+    properties: &HashMap<String, String>,
+) -> Dependency {
+    let f = |value: Option<String>| value.map(|v| interpolate(&v, properties));
+    Dependency {
+        group_id: f(dependency.group_id),
+        artifact_id: f(dependency.artifact_id),
+        version: f(dependency.version),
+        classifier: f(dependency.classifier),
+        dependency_type: f(dependency.dependency_type),
+        scope: f(dependency.scope),
+        system_path: f(dependency.system_path),
+        optional: f(dependency.optional),
+        ..dependency
+    }
+}
+
+fn activate(pom: &mut Model) {
+    let Some(profiles) = &pom.profiles else {
+        return;
+    };
+
+    let active = profiles
+        .profiles
+        .iter()
+        .filter(|p| {
+            p.activation
+                .as_ref()
+                .and_then(|a| a.active_by_default)
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for profile in active {
+        inject(pom, profile);
+    }
+}
+
+fn inject(pom: &mut Model, profile: Profile) {
+    pom.modules = merge_option(profile.modules, pom.modules.take(), |p, m| Modules {
+        modules: m.modules.into_iter().chain(p.modules).collect(),
+        unknown: p.unknown,
+    });
+    pom.distribution_management = merge_option(
+        profile.distribution_management,
+        pom.distribution_management.take(),
+        merge_distribution_management,
+    );
+    pom.properties = merge_option(profile.properties, pom.properties.take(), |p, m| {
+        Properties(merge_map(p.0, m.0))
+    });
+    pom.dependency_management = merge_option(
+        profile.dependency_management,
+        pom.dependency_management.take(),
+        merge_dependency_management,
+    );
+    pom.dependencies = merge_option(
+        profile.dependencies,
+        pom.dependencies.take(),
+        merge_dependencies,
+    );
+    pom.repositories = merge_option(
+        profile.repositories,
+        pom.repositories.take(),
+        merge_repositories,
+    );
+    pom.plugin_repositories = merge_option(
+        profile.plugin_repositories.map(|r| PluginRepositories {
+            plugin_repositories: r.repositories,
+            unknown: r.unknown,
+        }),
+        pom.plugin_repositories.take(),
+        merge_plugin_repositories,
+    );
+    pom.build = merge_option(
+        profile.build.map(|b| Build {
+            default_goal: b.default_goal,
+            directory: b.directory,
+            final_name: b.final_name,
+            filters: b.filters,
+            resources: b.resources,
+            test_resources: b.test_resources,
+            plugin_management: b.plugin_management,
+            plugins: b.plugins,
+            source_directory: None,
+            script_source_directory: None,
+            test_source_directory: None,
+            output_directory: None,
+            test_output_directory: None,
+            extensions: None,
+            unknown: b.unknown,
+        }),
+        pom.build.take(),
+        merge_build,
+    );
+    pom.reporting = merge_option(profile.reporting, pom.reporting.take(), merge_reporting);
+}
+
+fn manage(pom: &mut Model) {
+    let managed = pom
+        .dependency_management
+        .as_ref()
+        .and_then(|d| d.dependencies.as_ref())
+        .map(|d| d.dependencies.as_slice())
+        .unwrap_or_default();
+    if let Some(dependencies) = &mut pom.dependencies {
+        for dependency in &mut dependencies.dependencies {
+            let key = dependency_key(dependency);
+            if let Some(managed) = managed.iter().find(|m| dependency_key(m) == key) {
+                let managed = managed.clone();
+                dependency.version = dependency.version.take().or(managed.version);
+                dependency.scope = dependency.scope.take().or(managed.scope);
+                dependency.system_path = dependency.system_path.take().or(managed.system_path);
+                dependency.optional = dependency.optional.take().or(managed.optional);
+                dependency.exclusions = dependency.exclusions.take().or(managed.exclusions);
+            }
+        }
+    }
+
+    if let Some(build) = &mut pom.build {
+        let managed = build
+            .plugin_management
+            .as_ref()
+            .and_then(|p| p.plugins.as_ref())
+            .map(|p| p.plugins.as_slice())
+            .unwrap_or_default();
+        if let Some(plugins) = &mut build.plugins {
+            plugins.plugins = plugins
+                .plugins
+                .drain(..)
+                .map(|plugin| {
+                    let key = plugin_key(&plugin);
+                    match managed.iter().find(|m| plugin_key(m) == key) {
+                        Some(managed) => merge_plugin(plugin, managed.clone()),
+                        None => plugin,
+                    }
+                })
+                .collect();
+        }
+    }
+}
+
+pub fn dependency_key(
+    dependency: &Dependency,
+) -> (Option<String>, Option<String>, String, Option<String>) {
+    (
+        dependency.group_id.clone(),
+        dependency.artifact_id.clone(),
+        dependency
+            .dependency_type
+            .clone()
+            .unwrap_or_else(|| "jar".to_string()),
+        dependency.classifier.clone(),
+    )
+}
+
+pub fn plugin_key(plugin: &Plugin) -> (String, Option<String>) {
+    (
+        plugin
+            .group_id
+            .clone()
+            .unwrap_or_else(|| "org.apache.maven.plugins".to_string()),
+        plugin.artifact_id.clone(),
+    )
+}
+
+fn inherit(child: Model, parent: Model) -> Model {
+    let artifact_id = child.artifact_id.clone().unwrap_or_default();
+    let append_path = parent.child_project_url_inherit_append_path.as_deref() != Some("false");
+
+    Model {
+        model_version: child.model_version.or(parent.model_version),
+        parent: child.parent,
+        group_id: child.group_id.or(parent.group_id),
+        artifact_id: child.artifact_id,
+        version: child.version.or(parent.version),
+        packaging: child.packaging,
+        name: child.name,
+        description: child.description.or(parent.description),
+        url: child
+            .url
+            .or_else(|| parent.url.map(|url| append(url, &artifact_id, append_path))),
+        inception_year: child.inception_year.or(parent.inception_year),
+        organization: child.organization.or(parent.organization),
+        licenses: child
+            .licenses
+            .filter(|l| !l.licenses.is_empty())
+            .or(parent.licenses),
+        developers: child
+            .developers
+            .filter(|d| !d.developers.is_empty())
+            .or(parent.developers),
+        contributors: child
+            .contributors
+            .filter(|c| !c.contributors.is_empty())
+            .or(parent.contributors),
+        mailing_lists: child
+            .mailing_lists
+            .filter(|m| !m.mailing_lists.is_empty())
+            .or(parent.mailing_lists),
+        prerequisites: child.prerequisites,
+        modules: child.modules,
+        scm: merge_option(child.scm, parent.scm, |c, p| {
+            inherit_scm(c, p, &artifact_id)
+        }),
+        issue_management: child.issue_management.or(parent.issue_management),
+        ci_management: child.ci_management.or(parent.ci_management),
+        distribution_management: merge_option(
+            child.distribution_management,
+            parent.distribution_management.map(|mut d| {
+                d.relocation = None;
+                if let Some(site) = &mut d.site {
+                    let append_path =
+                        site.child_site_url_inherit_append_path.as_deref() != Some("false");
+                    site.url = site
+                        .url
+                        .take()
+                        .map(|u| append(u, &artifact_id, append_path));
+                }
+                d
+            }),
+            merge_distribution_management,
+        ),
+        properties: merge_option(child.properties, parent.properties, |c, p| {
+            Properties(merge_map(c.0, p.0))
+        }),
+        dependency_management: merge_option(
+            child.dependency_management,
+            parent.dependency_management,
+            merge_dependency_management,
+        ),
+        dependencies: merge_option(child.dependencies, parent.dependencies, merge_dependencies),
+        repositories: merge_option(child.repositories, parent.repositories, merge_repositories),
+        plugin_repositories: merge_option(
+            child.plugin_repositories,
+            parent.plugin_repositories,
+            merge_plugin_repositories,
+        ),
+        build: merge_option(
+            child.build,
+            parent.build.map(|mut b| {
+                b.plugins = b.plugins.map(without_uninherited);
+                b
+            }),
+            merge_build,
+        ),
+        reporting: merge_option(child.reporting, parent.reporting, merge_reporting),
+        reports: child.reports,
+        profiles: child.profiles,
+        schema_location: child.schema_location.or(parent.schema_location),
+        child_project_url_inherit_append_path: child
+            .child_project_url_inherit_append_path
+            .or(parent.child_project_url_inherit_append_path),
+        unknown: child.unknown,
+    }
+}
+
+fn inherit_scm(child: Scm, parent: Scm, artifact_id: &str) -> Scm {
+    let connection = parent.child_scm_connection_inherit_append_path.as_deref() != Some("false");
+    let developer_connection = parent
+        .child_scm_developer_connection_inherit_append_path
+        .as_deref()
+        != Some("false");
+    let url = parent.child_scm_url_inherit_append_path.as_deref() != Some("false");
+
+    Scm {
+        connection: child.connection.or_else(|| {
+            parent
+                .connection
+                .map(|c| append(c, artifact_id, connection))
+        }),
+        developer_connection: child.developer_connection.or_else(|| {
+            parent
+                .developer_connection
+                .map(|c| append(c, artifact_id, developer_connection))
+        }),
+        tag: child.tag.or(parent.tag),
+        url: child
+            .url
+            .or_else(|| parent.url.map(|u| append(u, artifact_id, url))),
+        child_scm_connection_inherit_append_path: child
+            .child_scm_connection_inherit_append_path
+            .or(parent.child_scm_connection_inherit_append_path),
+        child_scm_developer_connection_inherit_append_path: child
+            .child_scm_developer_connection_inherit_append_path
+            .or(parent.child_scm_developer_connection_inherit_append_path),
+        child_scm_url_inherit_append_path: child
+            .child_scm_url_inherit_append_path
+            .or(parent.child_scm_url_inherit_append_path),
+        unknown: child.unknown,
+    }
+}
+
+fn append(url: String, artifact_id: &str, append_path: bool) -> String {
+    if append_path && !artifact_id.is_empty() {
+        format!("{}/{artifact_id}", url.trim_end_matches('/'))
+    } else {
+        url
+    }
+}
+
+fn without_uninherited(plugins: Plugins) -> Plugins {
+    Plugins {
+        plugins: plugins
+            .plugins
+            .into_iter()
+            .filter(|p| p.inherited.as_deref() != Some("false"))
+            .collect(),
+        unknown: plugins.unknown,
+    }
+}
+
+fn merge_option<T>(
+    dominant: Option<T>,
+    recessive: Option<T>,
+    f: impl FnOnce(T, T) -> T,
+) -> Option<T> {
+    match (dominant, recessive) {
+        (Some(d), Some(r)) => Some(f(d, r)),
+        (d, r) => d.or(r),
+    }
+}
+
+fn merge_by<T, K, F, M>(dominant: Vec<T>, recessive: Vec<T>, key: F, merge: M) -> Vec<T>
+where
+    K: Eq + Hash,
+    F: Fn(&T) -> K,
+    M: Fn(T, T) -> T,
+{
+    let mut merged = IndexMap::new();
+    for item in dominant {
+        merged.insert(key(&item), item);
+    }
+
+    for item in recessive {
+        match merged.shift_remove_full(&key(&item)) {
+            Some((index, k, d)) => {
+                merged.shift_insert(index, k, merge(d, item));
+            }
+            None => {
+                merged.insert(key(&item), item);
+            }
+        }
+    }
+
+    merged.into_values().collect()
+}
+
+fn merge_map(
+    dominant: IndexMap<String, Value>,
+    mut recessive: IndexMap<String, Value>,
+) -> IndexMap<String, Value> {
+    let mut merged = IndexMap::new();
+    for (key, value) in dominant {
+        let value = match (value, recessive.shift_remove(&key)) {
+            (Value::Map(d), Some(Value::Map(r))) => Value::Map(merge_map(d, r)),
+            (value, _) => value,
+        };
+        merged.insert(key, value);
+    }
+    merged.extend(recessive);
+    merged
+}
+
+fn merge_distribution_management(
+    dominant: DistributionManagement,
+    recessive: DistributionManagement,
+) -> DistributionManagement {
+    DistributionManagement {
+        repository: dominant.repository.or(recessive.repository),
+        snapshot_repository: dominant
+            .snapshot_repository
+            .or(recessive.snapshot_repository),
+        site: dominant.site.or(recessive.site),
+        relocation: dominant.relocation.or(recessive.relocation),
+        download_url: dominant.download_url.or(recessive.download_url),
+        status: dominant.status.or(recessive.status),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_dependency_management(
+    dominant: DependencyManagement,
+    recessive: DependencyManagement,
+) -> DependencyManagement {
+    DependencyManagement {
+        dependencies: merge_option(
+            dominant.dependencies,
+            recessive.dependencies,
+            merge_dependencies,
+        ),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_dependencies(dominant: Dependencies, recessive: Dependencies) -> Dependencies {
+    Dependencies {
+        dependencies: merge_by(
+            dominant.dependencies,
+            recessive.dependencies,
+            dependency_key,
+            |d, _| d,
+        ),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_repositories(dominant: Repositories, recessive: Repositories) -> Repositories {
+    Repositories {
+        repositories: merge_by(
+            dominant.repositories,
+            recessive.repositories,
+            |r| r.id.clone(),
+            |d, _| d,
+        ),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_plugin_repositories(
+    dominant: PluginRepositories,
+    recessive: PluginRepositories,
+) -> PluginRepositories {
+    PluginRepositories {
+        plugin_repositories: merge_by(
+            dominant.plugin_repositories,
+            recessive.plugin_repositories,
+            |r| r.id.clone(),
+            |d, _| d,
+        ),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_build(dominant: Build, recessive: Build) -> Build {
+    Build {
+        default_goal: dominant.default_goal.or(recessive.default_goal),
+        directory: dominant.directory.or(recessive.directory),
+        final_name: dominant.final_name.or(recessive.final_name),
+        filters: dominant
+            .filters
+            .filter(|f| !f.filters.is_empty())
+            .or(recessive.filters),
+        resources: dominant
+            .resources
+            .filter(|r| !r.resources.is_empty())
+            .or(recessive.resources),
+        test_resources: dominant
+            .test_resources
+            .filter(|r| !r.test_resources.is_empty())
+            .or(recessive.test_resources),
+        plugin_management: merge_option(
+            dominant.plugin_management,
+            recessive.plugin_management,
+            |d, r| PluginManagement {
+                plugins: merge_option(d.plugins, r.plugins, merge_plugins),
+                unknown: d.unknown,
+            },
+        ),
+        plugins: merge_option(dominant.plugins, recessive.plugins, merge_plugins),
+        source_directory: dominant.source_directory.or(recessive.source_directory),
+        script_source_directory: dominant
+            .script_source_directory
+            .or(recessive.script_source_directory),
+        test_source_directory: dominant
+            .test_source_directory
+            .or(recessive.test_source_directory),
+        output_directory: dominant.output_directory.or(recessive.output_directory),
+        test_output_directory: dominant
+            .test_output_directory
+            .or(recessive.test_output_directory),
+        extensions: merge_option(dominant.extensions, recessive.extensions, |d, r| {
+            Extensions {
+                extensions: merge_by(
+                    d.extensions,
+                    r.extensions,
+                    |e| (e.group_id.clone(), e.artifact_id.clone()),
+                    |d, _| d,
+                ),
+                unknown: d.unknown,
+            }
+        }),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_plugins(dominant: Plugins, recessive: Plugins) -> Plugins {
+    Plugins {
+        plugins: merge_by(
+            dominant.plugins,
+            recessive.plugins,
+            plugin_key,
+            merge_plugin,
+        ),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_plugin(dominant: Plugin, recessive: Plugin) -> Plugin {
+    Plugin {
+        group_id: dominant.group_id.or(recessive.group_id),
+        artifact_id: dominant.artifact_id.or(recessive.artifact_id),
+        version: dominant.version.or(recessive.version),
+        extensions: dominant.extensions.or(recessive.extensions),
+        inherited: dominant.inherited.or(recessive.inherited),
+        configuration: merge_option(dominant.configuration, recessive.configuration, |d, r| {
+            Configuration(merge_map(d.0, r.0))
+        }),
+        dependencies: merge_option(
+            dominant.dependencies,
+            recessive.dependencies,
+            merge_dependencies,
+        ),
+        executions: merge_option(dominant.executions, recessive.executions, |d, r| {
+            PluginExecutions {
+                executions: merge_by(
+                    d.executions,
+                    r.executions,
+                    |e| e.id.clone().unwrap_or_else(|| "default".to_string()),
+                    merge_execution,
+                ),
+                unknown: d.unknown,
+            }
+        }),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_execution(dominant: PluginExecution, recessive: PluginExecution) -> PluginExecution {
+    PluginExecution {
+        id: dominant.id.or(recessive.id),
+        goals: merge_option(dominant.goals, recessive.goals, |d, r| {
+            let mut goals = r.goals;
+            goals.retain(|g| !d.goals.contains(g));
+            Goals {
+                goals: goals.into_iter().chain(d.goals).collect(),
+                unknown: d.unknown,
+            }
+        }),
+        phase: dominant.phase.or(recessive.phase),
+        inherited: dominant.inherited.or(recessive.inherited),
+        configuration: merge_option(dominant.configuration, recessive.configuration, |d, r| {
+            Configuration(merge_map(d.0, r.0))
+        }),
+        unknown: dominant.unknown,
+    }
+}
+
+fn merge_reporting(dominant: Reporting, recessive: Reporting) -> Reporting {
+    Reporting {
+        output_directory: dominant.output_directory.or(recessive.output_directory),
+        exclude_defaults: dominant.exclude_defaults.or(recessive.exclude_defaults),
+        plugins: merge_option(dominant.plugins, recessive.plugins, |d, r| ReportPlugins {
+            plugins: merge_by(
+                d.plugins,
+                r.plugins,
+                |p| (p.group_id.clone(), p.artifact_id.clone()),
+                |d, _| d,
+            ),
+            unknown: d.unknown,
+        }),
+        unknown: dominant.unknown,
+    }
+}
+
+fn properties(path: &Path, pom: &Model) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+
+    let basedir = fs::canonicalize(path)
+        .ok()
+        .and_then(|p| p.parent().map(|p| p.display().to_string()))
+        .unwrap_or_default();
+    properties.insert("basedir".to_string(), basedir.clone());
+    properties.insert("project.basedir".to_string(), basedir);
+
+    let mut project = vec![
+        ("groupId", pom.group_id.clone()),
+        ("artifactId", pom.artifact_id.clone()),
+        ("version", pom.version.clone()),
+        (
+            "packaging",
+            pom.packaging.clone().or(Some("jar".to_string())),
+        ),
+        ("name", pom.name.clone()),
+        ("description", pom.description.clone()),
+        ("url", pom.url.clone()),
+    ];
+    if let Some(parent) = &pom.parent {
+        project.extend([
+            ("parent.groupId", parent.group_id.clone()),
+            ("parent.artifactId", parent.artifact_id.clone()),
+            ("parent.version", parent.version.clone()),
+        ]);
+    }
+    project.extend([
+        (
+            "build.directory",
+            pom.build
+                .as_ref()
+                .and_then(|b| b.directory.clone())
+                .or(Some("${project.basedir}/target".to_string())),
+        ),
+        (
+            "build.finalName",
+            pom.build
+                .as_ref()
+                .and_then(|b| b.final_name.clone())
+                .or(Some("${project.artifactId}-${project.version}".to_string())),
+        ),
+    ]);
+    for (key, value) in project {
+        if let Some(value) = value {
+            properties.insert(format!("project.{key}"), value.clone());
+            properties.insert(format!("pom.{key}"), value);
+        }
+    }
+
+    for (key, value) in env::vars() {
+        properties.insert(format!("env.{key}"), value);
+    }
+
+    if let Some(p) = &pom.properties {
+        for (key, value) in &p.0 {
+            if let Value::String(value) = value {
+                properties.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    properties
+}
+
+fn interpolate(s: &str, properties: &HashMap<String, String>) -> String {
+    substitute(s, properties, &mut Vec::new())
+}
+
+fn substitute<'a>(
+    s: &str,
+    properties: &'a HashMap<String, String>,
+    stack: &mut Vec<&'a str>,
+) -> String {
+    let mut result = String::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+
+        result.push_str(&rest[..start]);
+        let key = &rest[(start + 2)..end];
+        match properties.get_key_value(key) {
+            Some((key, value)) if !stack.contains(&key.as_str()) => {
+                stack.push(key);
+                result.push_str(&substitute(value, properties, stack));
+                stack.pop();
+            }
+            _ => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[(end + 1)..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let dir = std::env::temp_dir().join(format!("junk-effective-{}", std::process::id()));
+        let repository = dir.join("repository");
+        let files = [
+            (
+                "repository/org/example/root/1/root-1.pom",
+                r#"<project>
+  <groupId>org.example</groupId>
+  <artifactId>root</artifactId>
+  <version>1</version>
+  <url>https://example.org</url>
+  <properties><java.version>17</java.version></properties>
+</project>"#,
+            ),
+            (
+                "repository/org/example/bom/2/bom-2.pom",
+                r#"<project>
+  <groupId>org.example</groupId>
+  <artifactId>bom</artifactId>
+  <version>2</version>
+  <properties><lib.version>2.1</lib.version></properties>
+  <dependencyManagement><dependencies>
+    <dependency><groupId>org.example</groupId><artifactId>lib</artifactId><version>${lib.version}</version></dependency>
+    <dependency><groupId>org.example</groupId><artifactId>core</artifactId><version>2.0</version></dependency>
+  </dependencies></dependencyManagement>
+</project>"#,
+            ),
+            (
+                "project/pom.xml",
+                r#"<project>
+  <parent><groupId>org.example</groupId><artifactId>root</artifactId><version>1</version><relativePath/></parent>
+  <artifactId>parent</artifactId>
+  <properties><bom.version>2</bom.version></properties>
+  <dependencyManagement><dependencies>
+    <dependency><groupId>org.example</groupId><artifactId>core</artifactId><version>1.0</version></dependency>
+    <dependency><groupId>org.example</groupId><artifactId>bom</artifactId><version>${bom.version}</version><type>pom</type><scope>import</scope></dependency>
+  </dependencies></dependencyManagement>
+  <profiles>
+    <profile>
+      <id>default</id>
+      <activation><activeByDefault>true</activeByDefault></activation>
+      <properties><java.version>21</java.version></properties>
+    </profile>
+    <profile>
+      <id>other</id>
+      <properties><java.version>11</java.version></properties>
+    </profile>
+  </profiles>
+</project>"#,
+            ),
+            (
+                "project/child/pom.xml",
+                r#"<project>
+  <parent><groupId>org.example</groupId><artifactId>parent</artifactId><version>1</version></parent>
+  <artifactId>child</artifactId>
+  <dependencies><dependency><groupId>org.example</groupId><artifactId>lib</artifactId></dependency></dependencies>
+</project>"#,
+            ),
+        ];
+        for (name, text) in files {
+            let path = dir.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+
+        let mut pom = resolve(&dir.join("project/child/pom.xml"), &repository).unwrap();
+        manage(&mut pom);
+        assert_eq!(pom.group_id.as_deref(), Some("org.example"));
+        assert_eq!(pom.version.as_deref(), Some("1"));
+        assert_eq!(pom.url.as_deref(), Some("https://example.org/parent/child"));
+
+        let properties = pom.properties.as_ref().unwrap();
+        assert!(matches!(&properties.0["java.version"], Value::String(v) if v == "21"));
+
+        let managed = pom.dependency_management.as_ref().unwrap();
+        let managed = &managed.dependencies.as_ref().unwrap().dependencies;
+        let versions = managed
+            .iter()
+            .map(|d| (d.artifact_id.as_deref(), d.version.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            [(Some("core"), Some("1.0")), (Some("lib"), Some("2.1"))]
+        );
+
+        let dependencies = &pom.dependencies.as_ref().unwrap().dependencies;
+        assert_eq!(dependencies[0].version.as_deref(), Some("2.1"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let properties = HashMap::from([
+            ("a".to_string(), "${b}-${c}".to_string()),
+            ("b".to_string(), "B".to_string()),
+            ("c".to_string(), "${a}".to_string()),
+        ]);
+
+        assert_eq!(interpolate("x${b}y", &properties), "xBy");
+        assert_eq!(interpolate("${a}", &properties), "B-${a}");
+        assert_eq!(interpolate("${unknown} ${b", &properties), "${unknown} ${b");
+    }
+
+    #[test]
+    fn test_merge_by() {
+        let merged = merge_by(
+            vec![("a", 1), ("b", 2)],
+            vec![("c", 3), ("a", 4)],
+            |(k, _)| *k,
+            |(k, d), (_, r)| (k, d * 10 + r),
+        );
+        assert_eq!(merged, vec![("a", 14), ("b", 2), ("c", 3)]);
+    }
+
+    #[test]
+    fn test_merge_map() {
+        let dominant = IndexMap::from([
+            ("a".to_string(), Value::String("1".to_string())),
+            (
+                "m".to_string(),
+                Value::Map(IndexMap::from([(
+                    "x".to_string(),
+                    Value::String("2".to_string()),
+                )])),
+            ),
+        ]);
+        let recessive = IndexMap::from([
+            ("a".to_string(), Value::String("3".to_string())),
+            (
+                "m".to_string(),
+                Value::Map(IndexMap::from([(
+                    "y".to_string(),
+                    Value::String("4".to_string()),
+                )])),
+            ),
+            ("b".to_string(), Value::String("5".to_string())),
+        ]);
+
+        let merged = merge_map(dominant, recessive);
+        assert_eq!(merged.keys().collect::<Vec<_>>(), ["a", "m", "b"]);
+        assert!(matches!(&merged["a"], Value::String(s) if s == "1"));
+        assert!(matches!(&merged["m"], Value::Map(m) if m.len() == 2));
+    }
+}
@@ -0,0 +1,412 @@
+use super::effective_pom::{self, dependency_key, plugin_key};
+use super::maven::*;
+use crate::{batch, git};
+use anyhow::{bail, Result};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const PATTERNS: &[&str] = &["pom.xml"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    #[arg(long)]
+    repository: Option<PathBuf>,
+
+    #[arg(long)]
+    release: bool,
+
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[command(flatten)]
+    selection: batch::Selection,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct Finding {
+    file: String,
+    rule: &'static str,
+    element: String,
+    message: String,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let repository = match args.repository {
+        Some(repository) => repository,
+        None => effective_pom::default_repository()?,
+    };
+
+    let paths = batch::expand(&args.paths, &args.selection, PATTERNS)?;
+    let results = paths
+        .par_iter()
+        .map(|path| lint_file(path, &repository, args.release))
+        .collect::<Vec<_>>();
+
+    let mut findings = Vec::new();
+    let mut failed = 0;
+    for (path, result) in paths.iter().zip(results) {
+        match result {
+            Ok(found) => findings.extend(found),
+            Err(e) => {
+                failed += 1;
+                batch::report_error(path, &e);
+            }
+        }
+    }
+
+    match args.format {
+        Format::Text => {
+            for f in &findings {
+                println!("{}: {}: {} [{}]", f.file, f.element, f.message, f.rule);
+            }
+        }
+        Format::Json => {
+            serde_json::to_writer_pretty(io::stdout().lock(), &findings)?;
+            println!();
+        }
+    }
+
+    match (findings.len(), failed) {
+        (0, 0) => Ok(()),
+        (found, 0) => bail!("{found} problem(s) found"),
+        (found, failed) => bail!("{found} problem(s) found, {failed} file(s) failed"),
+    }
+}
+
+fn lint_file(path: &Path, repository: &Path, release: bool) -> Result<Vec<Finding>> {
+    let (_, pom, unknown) = super::read::<Model>(path)?;
+    // Resolving reports unrecognized elements for this POM and each POM it inherits from.
+    let effective = match effective_pom::resolve(path, repository) {
+        Ok(effective) => effective,
+        Err(e) => {
+            super::warn_unknown(path, &unknown);
+            let warning =
+                format!("failed to resolve the effective POM, linting it as written: {e:#}");
+            batch::report_warnings(path, &[warning]);
+            pom.clone()
+        }
+    };
+    let release = release || on_release_branch(path);
+
+    Ok(lint(&pom, &effective, release)
+        .into_iter()
+        .map(|(rule, element, message)| Finding {
+            file: path.display().to_string(),
+            rule,
+            element,
+            message,
+        })
+        .collect())
+}
+
+fn lint(pom: &Model, effective: &Model, release: bool) -> Vec<(&'static str, String, String)> {
+    let mut findings = Vec::new();
+
+    let managed_dependencies = effective
+        .dependency_management
+        .iter()
+        .flat_map(|d| &d.dependencies)
+        .flat_map(|d| &d.dependencies)
+        .map(dependency_key)
+        .collect::<HashSet<_>>();
+    let managed_plugins = effective
+        .build
+        .iter()
+        .flat_map(|b| {
+            b.plugin_management
+                .iter()
+                .flat_map(|p| &p.plugins)
+                .chain(&b.plugins)
+        })
+        .flat_map(|p| &p.plugins)
+        .filter(|p| p.version.is_some())
+        .map(plugin_key)
+        .collect::<HashSet<_>>();
+
+    let mut dependencies = Vec::new();
+    let mut plugins = Vec::new();
+    collect(
+        "project",
+        &pom.dependencies,
+        &pom.dependency_management,
+        &mut dependencies,
+    );
+    if let Some(build) = &pom.build {
+        collect_plugins(
+            "project/build",
+            &build.plugins,
+            &build.plugin_management,
+            &mut plugins,
+        );
+    }
+    for (i, profile) in pom.profiles.iter().flat_map(|p| &p.profiles).enumerate() {
+        let base = format!("project/profiles/profile[{}]", i + 1);
+        collect(
+            &base,
+            &profile.dependencies,
+            &profile.dependency_management,
+            &mut dependencies,
+        );
+        if let Some(build) = &profile.build {
+            collect_plugins(
+                &format!("{base}/build"),
+                &build.plugins,
+                &build.plugin_management,
+                &mut plugins,
+            );
+        }
+    }
+
+    let mut seen = HashMap::new();
+    for (element, managed, dependency) in &dependencies {
+        let key = dependency_key(dependency);
+        let name = format!(
+            "{}:{}",
+            dependency.group_id.as_deref().unwrap_or_default(),
+            dependency.artifact_id.as_deref().unwrap_or_default()
+        );
+
+        let scope = (element.rsplit_once('/').map(|(s, _)| s), *managed);
+        if let Some(first) = seen.insert((scope, key.clone()), element) {
+            findings.push((
+                "duplicate-dependency",
+                element.clone(),
+                format!("{name} is already declared at {first}"),
+            ));
+        }
+
+        if !managed && dependency.version.is_some() && managed_dependencies.contains(&key) {
+            findings.push((
+                "managed-version",
+                format!("{element}/version"),
+                format!("version of {name} is already managed"),
+            ));
+        }
+
+        if dependency.scope.as_deref() == Some("system") && dependency.system_path.is_some() {
+            findings.push((
+                "system-path",
+                format!("{element}/systemPath"),
+                format!("{name} uses system scope with systemPath"),
+            ));
+        }
+    }
+
+    for (element, managed, plugin) in &plugins {
+        if plugin.version.is_none() && (*managed || !managed_plugins.contains(&plugin_key(plugin)))
+        {
+            findings.push((
+                "unversioned-plugin",
+                element.clone(),
+                format!(
+                    "plugin {} has no version",
+                    plugin.artifact_id.as_deref().unwrap_or_default()
+                ),
+            ));
+        }
+    }
+
+    let mut literals: HashMap<(&str, &str), Vec<&str>> = HashMap::new();
+    for (element, _, dependency) in &dependencies {
+        if let (Some(group_id), Some(version)) = (&dependency.group_id, &dependency.version) {
+            if !version.contains("${") {
+                literals
+                    .entry((group_id, version))
+                    .or_default()
+                    .push(element);
+            }
+        }
+    }
+    let mut literals = literals
+        .into_iter()
+        .filter(|(_, e)| e.len() > 1)
+        .collect::<Vec<_>>();
+    literals.sort();
+    for ((group_id, version), elements) in literals {
+        for element in elements {
+            findings.push((
+                "hardcoded-version",
+                format!("{element}/version"),
+                format!("version {version} is repeated for {group_id}, consider a property"),
+            ));
+        }
+    }
+
+    if release {
+        let versions = [("project/version", &pom.version)]
+            .into_iter()
+            .chain(
+                pom.parent
+                    .iter()
+                    .map(|p| ("project/parent/version", &p.version)),
+            )
+            .map(|(e, v)| (e.to_string(), v))
+            .chain(
+                dependencies
+                    .iter()
+                    .map(|(e, _, d)| (format!("{e}/version"), &d.version)),
+            )
+            .chain(
+                plugins
+                    .iter()
+                    .map(|(e, _, p)| (format!("{e}/version"), &p.version)),
+            );
+        for (element, version) in versions {
+            if let Some(version) = version.as_ref().filter(|v| v.ends_with("-SNAPSHOT")) {
+                findings.push((
+                    "snapshot-version",
+                    element,
+                    format!("SNAPSHOT version {version} on a release branch"),
+                ));
+            }
+        }
+    }
+
+    findings
+}
+
+fn collect<'a>(
+    base: &str,
+    dependencies: &'a Option<Dependencies>,
+    dependency_management: &'a Option<DependencyManagement>,
+    out: &mut Vec<(String, bool, &'a Dependency)>,
+) {
+    for (i, d) in dependencies
+        .iter()
+        .flat_map(|d| &d.dependencies)
+        .enumerate()
+    {
+        out.push((
+            format!("{base}/dependencies/dependency[{}]", i + 1),
+            false,
+            d,
+        ));
+    }
+
+    let managed = dependency_management
+        .iter()
+        .flat_map(|d| &d.dependencies)
+        .flat_map(|d| &d.dependencies);
+    for (i, d) in managed.enumerate() {
+        out.push((
+            format!(
+                "{base}/dependencyManagement/dependencies/dependency[{}]",
+                i + 1
+            ),
+            true,
+            d,
+        ));
+    }
+}
+
+fn collect_plugins<'a>(
+    base: &str,
+    plugins: &'a Option<Plugins>,
+    plugin_management: &'a Option<PluginManagement>,
+    out: &mut Vec<(String, bool, &'a Plugin)>,
+) {
+    for (i, p) in plugins.iter().flat_map(|p| &p.plugins).enumerate() {
+        out.push((format!("{base}/plugins/plugin[{}]", i + 1), false, p));
+    }
+
+    let managed = plugin_management
+        .iter()
+        .flat_map(|p| &p.plugins)
+        .flat_map(|p| &p.plugins);
+    for (i, p) in managed.enumerate() {
+        out.push((
+            format!("{base}/pluginManagement/plugins/plugin[{}]", i + 1),
+            true,
+            p,
+        ));
+    }
+}
+
+fn on_release_branch(path: &Path) -> bool {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    git::branch(dir)
+        .ok()
+        .flatten()
+        .is_some_and(|branch| branch.starts_with("release"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_lint() {
+        let pom: Model = yaserde::de::from_str(
+            r#"<project xmlns="http://maven.apache.org/POM/4.0.0">
+                <version>1.0-SNAPSHOT</version>
+                <dependencyManagement><dependencies>
+                    <dependency><groupId>g</groupId><artifactId>a</artifactId><version>1</version></dependency>
+                    <dependency><groupId>g</groupId><artifactId>b</artifactId><version>1</version></dependency>
+                </dependencies></dependencyManagement>
+                <dependencies>
+                    <dependency><groupId>g</groupId><artifactId>a</artifactId><version>2</version></dependency>
+                    <dependency><groupId>g</groupId><artifactId>a</artifactId></dependency>
+                    <dependency><groupId>s</groupId><artifactId>s</artifactId><scope>system</scope><systemPath>/x.jar</systemPath></dependency>
+                </dependencies>
+                <build><plugins><plugin><artifactId>maven-jar-plugin</artifactId></plugin></plugins></build>
+            </project>"#,
+        )
+        .unwrap();
+
+        let findings = lint(&pom, &pom, true)
+            .into_iter()
+            .map(|(rule, element, _)| format!("{rule} {element}"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings,
+            [
+                "managed-version project/dependencies/dependency[1]/version",
+                "duplicate-dependency project/dependencies/dependency[2]",
+                "system-path project/dependencies/dependency[3]/systemPath",
+                "unversioned-plugin project/build/plugins/plugin[1]",
+                "hardcoded-version project/dependencyManagement/dependencies/dependency[1]/version",
+                "hardcoded-version project/dependencyManagement/dependencies/dependency[2]/version",
+                "snapshot-version project/version",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_file_without_parent() {
+        let dir = std::env::temp_dir().join(format!("junk-lint-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pom.xml");
+        fs::write(
+            &path,
+            r#"<project>
+  <parent><groupId>org.example</groupId><artifactId>missing</artifactId><version>1</version></parent>
+  <artifactId>child</artifactId>
+  <dependencies>
+    <dependency><groupId>g</groupId><artifactId>a</artifactId></dependency>
+    <dependency><groupId>g</groupId><artifactId>a</artifactId></dependency>
+  </dependencies>
+</project>"#,
+        )
+        .unwrap();
+
+        let findings = lint_file(&path, &dir.join("repository"), false).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let rules = findings.iter().map(|f| f.rule).collect::<Vec<_>>();
+        assert_eq!(rules, ["duplicate-dependency"]);
+    }
+}
@@ -1,4 +1,5 @@
 use indexmap::IndexMap as Map;
+use std::cell::RefCell;
 use std::io::{Read, Write};
 use xml::attribute::OwnedAttribute;
 use xml::namespace::Namespace;
@@ -7,12 +8,15 @@ use yaserde::ser::Serializer as YaSerializer;
 use yaserde::{YaDeserialize, YaSerialize};
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
-#[derive(YaDeserialize, YaSerialize)]
+pub const NAMESPACE: &str = "http://maven.apache.org/POM/4.0.0";
+
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(
     namespace = "http://maven.apache.org/POM/4.0.0",
     namespace = "xsi: http://www.w3.org/2001/XMLSchema-instance",
     rename = "project"
 )]
+#[serde(rename_all = "camelCase")]
 pub struct Model {
     #[yaserde(rename = "modelVersion")]
     pub model_version: Option<String>,
@@ -75,11 +79,17 @@ pub struct Model {
     pub schema_location: Option<String>,
 
     #[yaserde(attribute, rename = "child.project.url.inherit.append.path")]
+    #[serde(rename = "child.project.url.inherit.append.path")]
     pub child_project_url_inherit_append_path: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Parent {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
@@ -91,40 +101,61 @@ pub struct Parent {
 
     #[yaserde(rename = "relativePath")]
     pub relative_path: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Organization {
     pub name: Option<String>,
     pub url: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Licenses {
     #[yaserde(rename = "license")]
     pub licenses: Vec<License>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct License {
     pub name: Option<String>,
     pub url: Option<String>,
     pub distribution: Option<String>,
     pub comments: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Developers {
     #[yaserde(rename = "developer")]
     pub developers: Vec<Developer>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Developer {
     pub id: Option<String>,
     pub name: Option<String>,
@@ -138,24 +169,35 @@ pub struct Developer {
     pub roles: Option<Roles>,
     pub timezone: Option<String>,
     pub properties: Option<Properties>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Roles {
     #[yaserde(rename = "role")]
     pub roles: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Contributors {
     #[yaserde(rename = "contributor")]
     pub contributors: Vec<Contributor>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Contributor {
     pub name: Option<String>,
     pub email: Option<String>,
@@ -168,17 +210,25 @@ pub struct Contributor {
     pub roles: Option<Roles>,
     pub timezone: Option<String>,
     pub properties: Option<Properties>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct MailingLists {
     #[yaserde(rename = "mailing_list")]
     pub mailing_lists: Vec<MailingList>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct MailingList {
     pub name: Option<String>,
     pub subscribe: Option<String>,
@@ -188,30 +238,46 @@ pub struct MailingList {
 
     #[yaserde(rename = "otherArchives")]
     pub other_archives: Option<OtherArchives>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct OtherArchives {
     #[yaserde(rename = "otherArchive")]
     pub other_archives: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Prerequisites {
     pub maven: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Modules {
     #[yaserde(rename = "module")]
     pub modules: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Scm {
     pub connection: Option<String>,
 
@@ -222,41 +288,63 @@ pub struct Scm {
     pub url: Option<String>,
 
     #[yaserde(rename = "child.scm.connection.inherit.append.path")]
+    #[serde(rename = "child.scm.connection.inherit.append.path")]
     pub child_scm_connection_inherit_append_path: Option<String>,
 
     #[yaserde(rename = "child.scm.developerConnection.inherit.append.path")]
+    #[serde(rename = "child.scm.developerConnection.inherit.append.path")]
     pub child_scm_developer_connection_inherit_append_path: Option<String>,
 
     #[yaserde(rename = "child.scm.url.inherit.append.path")]
+    #[serde(rename = "child.scm.url.inherit.append.path")]
     pub child_scm_url_inherit_append_path: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct IssueManagement {
     pub system: Option<String>,
     pub url: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct CiManagement {
     pub system: Option<String>,
     pub url: Option<String>,
     pub notifiers: Option<Notifiers>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Notifiers {
     #[yaserde(rename = "notifier")]
     pub notifiers: Vec<Notifier>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Notifier {
     #[yaserde(rename = "type")]
+    #[serde(rename = "type")]
     pub notifier_type: Option<String>,
 
     pub address: Option<String>,
@@ -274,10 +362,15 @@ pub struct Notifier {
     pub send_on_warning: Option<bool>,
 
     pub configuration: Option<Configuration>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct DistributionManagement {
     pub repository: Option<DeploymentRepository>,
 
@@ -291,10 +384,15 @@ pub struct DistributionManagement {
     pub download_url: Option<String>,
 
     pub status: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct DeploymentRepository {
     #[yaserde(rename = "uniqueVersion")]
     pub unique_version: Option<bool>,
@@ -305,10 +403,15 @@ pub struct DeploymentRepository {
     pub name: Option<String>,
     pub url: Option<String>,
     pub layout: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct RepositoryPolicy {
     pub enabled: Option<String>,
 
@@ -317,21 +420,32 @@ pub struct RepositoryPolicy {
 
     #[yaserde(rename = "checksumPolicy")]
     pub checksum_policy: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Site {
     pub id: Option<String>,
     pub name: Option<String>,
     pub url: Option<String>,
 
     #[yaserde(rename = "child.site.url.inherit.append.path")]
+    #[serde(rename = "child.site.url.inherit.append.path")]
     pub child_site_url_inherit_append_path: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Relocation {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
@@ -341,23 +455,36 @@ pub struct Relocation {
 
     pub version: Option<String>,
     pub message: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct DependencyManagement {
     pub dependencies: Option<Dependencies>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Dependencies {
     #[yaserde(rename = "dependency")]
     pub dependencies: Vec<Dependency>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Dependency {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
@@ -369,6 +496,7 @@ pub struct Dependency {
     pub classifier: Option<String>,
 
     #[yaserde(rename = "type")]
+    #[serde(rename = "type")]
     pub dependency_type: Option<String>,
 
     pub scope: Option<String>,
@@ -378,41 +506,60 @@ pub struct Dependency {
 
     pub optional: Option<String>,
     pub exclusions: Option<Exclusions>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Exclusions {
     #[yaserde(rename = "exclusion")]
     pub exclusions: Vec<Exclusion>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Exclusion {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
 
     #[yaserde(rename = "artifactId")]
     pub artifact_id: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Repositories {
     #[yaserde(rename = "repository")]
     pub repositories: Vec<Repository>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct PluginRepositories {
     #[yaserde(rename = "pluginRepository")]
     pub plugin_repositories: Vec<Repository>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Repository {
     pub releases: Option<RepositoryPolicy>,
     pub snapshots: Option<RepositoryPolicy>,
@@ -420,10 +567,15 @@ pub struct Repository {
     pub name: Option<String>,
     pub url: Option<String>,
     pub layout: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Build {
     #[yaserde(rename = "defaultGoal")]
     pub default_goal: Option<String>,
@@ -460,31 +612,45 @@ pub struct Build {
     pub test_output_directory: Option<String>,
 
     pub extensions: Option<Extensions>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Filters {
     #[yaserde(rename = "filter")]
     pub filters: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Resources {
     #[yaserde(rename = "resource")]
     pub resources: Vec<Resource>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct TestResources {
     #[yaserde(rename = "testResource")]
     pub test_resources: Vec<Resource>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Resource {
     #[yaserde(rename = "targetPath")]
     pub target_path: Option<String>,
@@ -493,41 +659,62 @@ pub struct Resource {
     pub directory: Option<String>,
 
     #[yaserde(rename = "includes")]
+    #[serde(default)]
     pub includes: Vec<Includes>,
 
     #[yaserde(rename = "excludes")]
+    #[serde(default)]
     pub excludes: Vec<Excludes>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Includes {
     #[yaserde(rename = "include")]
     pub includes: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Excludes {
     #[yaserde(rename = "exclude")]
     pub excludes: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct PluginManagement {
     pub plugins: Option<Plugins>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Plugins {
     #[yaserde(rename = "plugin")]
     pub plugins: Vec<Plugin>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Plugin {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
@@ -542,41 +729,60 @@ pub struct Plugin {
     pub configuration: Option<Configuration>,
     pub dependencies: Option<Dependencies>,
     pub executions: Option<PluginExecutions>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct PluginExecutions {
     #[yaserde(rename = "execution")]
     pub executions: Vec<PluginExecution>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct PluginExecution {
     pub id: Option<String>,
     pub goals: Option<Goals>,
     pub phase: Option<String>,
     pub inherited: Option<String>,
     pub configuration: Option<Configuration>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Goals {
     #[yaserde(rename = "goal")]
     pub goals: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Extensions {
     #[yaserde(rename = "extension")]
     pub extensions: Vec<Extension>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Extension {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
@@ -585,10 +791,15 @@ pub struct Extension {
     pub artifact_id: Option<String>,
 
     pub version: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Reporting {
     #[yaserde(rename = "outputDirectory")]
     pub output_directory: Option<String>,
@@ -597,17 +808,25 @@ pub struct Reporting {
     pub exclude_defaults: Option<String>,
 
     pub plugins: Option<ReportPlugins>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct ReportPlugins {
     #[yaserde(rename = "reportPlugin")]
     pub plugins: Vec<ReportPlugin>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct ReportPlugin {
     #[yaserde(rename = "groupId")]
     pub group_id: Option<String>,
@@ -621,40 +840,59 @@ pub struct ReportPlugin {
 
     #[yaserde(rename = "reportSets")]
     pub report_sets: Option<ReportSets>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct ReportSets {
     #[yaserde(rename = "reportSet")]
     pub report_sets: Vec<ReportSet>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct ReportSet {
     pub id: Option<String>,
     pub reports: Option<Reports>,
     pub inherited: Option<String>,
     pub configuration: Option<Configuration>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Reports {
     #[yaserde(rename = "report")]
     pub reports: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
 pub struct Profiles {
     #[yaserde(rename = "profile")]
     pub profiles: Vec<Profile>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Profile {
     pub id: Option<String>,
     pub activation: Option<Activation>,
@@ -677,10 +915,14 @@ pub struct Profile {
     pub build: Option<BuildBase>,
     pub reporting: Option<Reporting>,
     // pub reports: Option<OldReports>,
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct Activation {
     #[yaserde(rename = "activeByDefault")]
     pub active_by_default: Option<bool>,
@@ -689,33 +931,53 @@ pub struct Activation {
     pub os: Option<ActivationOS>,
     pub property: Option<ActivationProperty>,
     pub file: Option<ActivationFile>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct ActivationOS {
     pub name: Option<String>,
     pub family: Option<String>,
     pub arch: Option<String>,
     pub version: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct ActivationProperty {
     pub name: Option<String>,
     pub value: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct ActivationFile {
     exists: Option<String>,
     missing: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
-#[derive(YaDeserialize, YaSerialize)]
+#[derive(Clone, YaDeserialize, YaSerialize, serde::Deserialize, serde::Serialize)]
 #[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+#[serde(rename_all = "camelCase")]
 pub struct BuildBase {
     #[yaserde(rename = "defaultGoal")]
     pub default_goal: Option<String>,
@@ -736,11 +998,16 @@ pub struct BuildBase {
     pub plugin_management: Option<PluginManagement>,
 
     pub plugins: Option<Plugins>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    #[serde(default, skip_serializing_if = "Unknown::is_empty")]
+    pub unknown: Unknown,
 }
 
 macro_rules! def_map_type {
     ($name:ident, $element:literal) => {
-        pub struct $name(Map<String, Value>);
+        #[derive(Clone, serde::Deserialize, serde::Serialize)]
+        pub struct $name(pub Map<String, Value>);
 
         impl YaDeserialize for $name {
             fn deserialize<R: Read>(reader: &mut YaDeserializer<R>) -> Result<Self, String> {
@@ -750,7 +1017,7 @@ macro_rules! def_map_type {
                     XmlEvent::StartElement { name, .. } => {
                         if let Some(ns) = &name.namespace {
                             match ns.as_str() {
-                                "http://maven.apache.org/POM/4.0.0" => {}
+                                NAMESPACE => {}
                                 bad_ns => {
                                     return Err(format!(
                                         "bad namespace for {} found {}",
@@ -785,10 +1052,14 @@ macro_rules! def_map_type {
             fn serialize<W: Write>(&self, writer: &mut YaSerializer<W>) -> Result<(), String> {
                 use xml::writer::XmlEvent;
 
+                let element = writer
+                    .get_start_event_name()
+                    .unwrap_or_else(|| $element.to_string());
+
                 writer
                     .write(
-                        XmlEvent::start_element($element)
-                            .default_ns("http://maven.apache.org/POM/4.0.0")
+                        XmlEvent::start_element(element.as_str())
+                            .default_ns(NAMESPACE)
                             .ns("xsi", "http://www.w3.org/2001/XMLSchema-instance"),
                     )
                     .map_err(|e| e.to_string())?;
@@ -818,11 +1089,64 @@ def_map_type!(Configuration, "configuration");
 // def_map_type!(OldGoals, "goals");
 // def_map_type!(OldReports, "reports");
 
+#[derive(Clone, serde::Serialize)]
+#[serde(untagged)]
 pub enum Value {
     String(String),
     Map(Map<String, Value>),
 }
 
+impl<'de> serde::Deserialize<'de> for Value {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a scalar or a map")
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<Value, E> {
+                Ok(Value::String(v.to_string()))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Value, E> {
+                Ok(Value::String(v.to_string()))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Value, E> {
+                Ok(Value::String(v.to_string()))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Value, E> {
+                Ok(Value::String(v.to_string()))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Value, E> {
+                Ok(Value::String(v.to_string()))
+            }
+
+            fn visit_unit<E: serde::de::Error>(self) -> Result<Value, E> {
+                Ok(Value::String(String::new()))
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<Value, A::Error> {
+                let mut map = Map::new();
+                while let Some((key, value)) = access.next_entry()? {
+                    map.insert(key, value);
+                }
+                Ok(Value::Map(map))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
 impl YaDeserialize for Value {
     fn deserialize<R: Read>(reader: &mut YaDeserializer<R>) -> Result<Self, String> {
         use xml::reader::XmlEvent;
@@ -864,6 +1188,309 @@ impl YaSerialize for Value {
     }
 }
 
+thread_local! {
+    static UNKNOWN: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+// A list is written as its items alone, unless it has unrecognized elements, which are kept next to
+// the items under `unknown`.
+macro_rules! def_list_serde {
+    ($name:ident, $field:ident, $element:literal) => {
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_list(serializer, $element, &self.$field, &self.unknown)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let ($field, unknown) = deserializer.deserialize_any(ListVisitor {
+                    element: $element,
+                    items: std::marker::PhantomData,
+                })?;
+                Ok(Self { $field, unknown })
+            }
+        }
+    };
+}
+
+def_list_serde!(Licenses, licenses, "license");
+def_list_serde!(Developers, developers, "developer");
+def_list_serde!(Roles, roles, "role");
+def_list_serde!(Contributors, contributors, "contributor");
+def_list_serde!(MailingLists, mailing_lists, "mailing_list");
+def_list_serde!(OtherArchives, other_archives, "otherArchive");
+def_list_serde!(Modules, modules, "module");
+def_list_serde!(Notifiers, notifiers, "notifier");
+def_list_serde!(Dependencies, dependencies, "dependency");
+def_list_serde!(Exclusions, exclusions, "exclusion");
+def_list_serde!(Repositories, repositories, "repository");
+def_list_serde!(PluginRepositories, plugin_repositories, "pluginRepository");
+def_list_serde!(Filters, filters, "filter");
+def_list_serde!(Resources, resources, "resource");
+def_list_serde!(TestResources, test_resources, "testResource");
+def_list_serde!(Includes, includes, "include");
+def_list_serde!(Excludes, excludes, "exclude");
+def_list_serde!(Plugins, plugins, "plugin");
+def_list_serde!(PluginExecutions, executions, "execution");
+def_list_serde!(Goals, goals, "goal");
+def_list_serde!(Extensions, extensions, "extension");
+def_list_serde!(ReportPlugins, plugins, "reportPlugin");
+def_list_serde!(ReportSets, report_sets, "reportSet");
+def_list_serde!(Reports, reports, "report");
+def_list_serde!(Profiles, profiles, "profile");
+
+fn serialize_list<S: serde::Serializer, T: serde::Serialize>(
+    serializer: S,
+    element: &str,
+    items: &[T],
+    unknown: &Unknown,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+
+    if unknown.is_empty() {
+        return serializer.collect_seq(items);
+    }
+    let mut map = serializer.serialize_map(Some(2))?;
+    map.serialize_entry(element, items)?;
+    map.serialize_entry("unknown", unknown)?;
+    map.end()
+}
+
+struct ListVisitor<T> {
+    element: &'static str,
+    items: std::marker::PhantomData<T>,
+}
+
+impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for ListVisitor<T> {
+    type Value = (Vec<T>, Unknown);
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a list of {} elements", self.element)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok((items, Unknown::default()))
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let mut list = (Vec::new(), Unknown::default());
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "unknown" => list.1 = map.next_value()?,
+                key if key == self.element => list.0 = map.next_value()?,
+                key => {
+                    let expected = format!("`{}` or `unknown`", self.element);
+                    return Err(A::Error::custom(format!(
+                        "unknown field `{key}`, expected {expected}"
+                    )));
+                }
+            }
+        }
+        Ok(list)
+    }
+}
+
+// Runs `f` and returns the paths of the elements it kept as unknown while deserializing.
+pub(crate) fn collect_unknown<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let outer = UNKNOWN.replace(Some(Vec::new()));
+    let value = f();
+    let unknown = UNKNOWN.replace(outer).unwrap_or_default();
+    (value, unknown)
+}
+
+#[derive(Clone, Default)]
+pub struct Unknown(pub Vec<Vec<xml::reader::XmlEvent>>);
+
+impl Unknown {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Each element declares only the namespaces it uses.
+    fn events(&self) -> impl Iterator<Item = xml::reader::XmlEvent> + '_ {
+        use xml::reader::XmlEvent;
+
+        self.0.iter().flatten().map(|event| match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                let mut used = Namespace::empty();
+                if name.prefix.is_none() {
+                    if let Some(uri) = namespace.get(xml::namespace::NS_NO_PREFIX) {
+                        used.put(xml::namespace::NS_NO_PREFIX, uri);
+                    }
+                }
+                let prefixes = attributes.iter().map(|a| &a.name).chain([name]);
+                for prefix in prefixes.filter_map(|n| n.prefix.as_deref()) {
+                    if let Some(uri) = namespace.get(prefix) {
+                        used.put(prefix, uri);
+                    }
+                }
+
+                XmlEvent::StartElement {
+                    name: name.clone(),
+                    attributes: attributes.clone(),
+                    namespace: used,
+                }
+            }
+            event => event.clone(),
+        })
+    }
+}
+
+// In JSON and YAML each unknown element is kept as an XML fragment, with the POM namespace implied.
+impl serde::Serialize for Unknown {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error, SerializeSeq};
+        use xml::reader::XmlEvent;
+
+        let mut fragments = Vec::new();
+        let mut writer = xml::EmitterConfig::new()
+            .write_document_declaration(false)
+            .create_writer(Vec::new());
+        let mut depth = 0;
+        for event in self.events() {
+            let event = match event {
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    mut namespace,
+                } => {
+                    depth += 1;
+                    if namespace.get(xml::namespace::NS_NO_PREFIX) == Some(NAMESPACE) {
+                        namespace.0.remove(xml::namespace::NS_NO_PREFIX);
+                    }
+                    XmlEvent::StartElement {
+                        name,
+                        attributes,
+                        namespace,
+                    }
+                }
+                XmlEvent::EndElement { name } => {
+                    depth -= 1;
+                    XmlEvent::EndElement { name }
+                }
+                event => event,
+            };
+
+            if let Some(event) = event.as_writer_event() {
+                writer.write(event).map_err(S::Error::custom)?;
+            }
+            if depth == 0 {
+                let fragment = std::mem::take(writer.inner_mut());
+                fragments.push(String::from_utf8(fragment).map_err(S::Error::custom)?);
+            }
+        }
+
+        let mut seq = serializer.serialize_seq(Some(fragments.len()))?;
+        for fragment in &fragments {
+            seq.serialize_element(fragment)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Unknown {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        use xml::reader::{EventReader, XmlEvent};
+
+        let mut elements = Vec::new();
+        for fragment in <Vec<String>>::deserialize(deserializer)? {
+            let input = format!("<unknown xmlns=\"{NAMESPACE}\">{fragment}</unknown>");
+            let mut events = Vec::new();
+            let mut depth = 0;
+            for event in EventReader::from_str(&input) {
+                match event.map_err(D::Error::custom)? {
+                    event @ XmlEvent::StartElement { .. } => {
+                        depth += 1;
+                        if depth > 1 {
+                            events.push(event);
+                        }
+                    }
+                    event @ XmlEvent::EndElement { .. } => {
+                        depth -= 1;
+                        if depth > 0 {
+                            events.push(event);
+                        }
+                        if depth == 1 {
+                            elements.push(std::mem::take(&mut events));
+                        }
+                    }
+                    event if depth > 1 => events.push(event),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self(elements))
+    }
+}
+
+impl YaDeserialize for Unknown {
+    fn deserialize<R: Read>(reader: &mut YaDeserializer<R>) -> Result<Self, String> {
+        use xml::reader::XmlEvent;
+
+        let parent = match reader.next_event()? {
+            XmlEvent::StartElement { name, .. } => name.local_name,
+            event => return Err(format!("unexpected {event:?}")),
+        };
+
+        let mut elements = Vec::new();
+        loop {
+            match reader.peek()? {
+                XmlEvent::StartElement { name, .. } => {
+                    let path = format!("{parent}/{}", name.borrow().repr_display());
+                    UNKNOWN.with_borrow_mut(|unknown| {
+                        if let Some(unknown) = unknown {
+                            unknown.push(path);
+                        }
+                    });
+
+                    let mut events = vec![reader.next_event()?];
+                    reader.skip_element(|e| events.push(e.clone()))?;
+                    elements.push(events);
+                }
+                XmlEvent::EndElement { .. } | XmlEvent::EndDocument => break,
+                _ => {
+                    reader.next_event()?;
+                }
+            }
+        }
+
+        Ok(Self(elements))
+    }
+}
+
+impl YaSerialize for Unknown {
+    fn serialize<W: Write>(&self, writer: &mut YaSerializer<W>) -> Result<(), String> {
+        for event in self.events() {
+            if let Some(event) = event.as_writer_event() {
+                writer.write(event).map_err(|e| e.to_string())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
 fn deserialize_map<R: Read>(reader: &mut YaDeserializer<R>) -> Result<Map<String, Value>, String> {
     use xml::reader::XmlEvent;
 
@@ -1002,4 +1629,21 @@ mod tests {
             } if p.0.is_empty()
         ));
     }
+
+    #[test]
+    fn test_unknown() {
+        let (plugin, unknown) = collect_unknown(|| {
+            from_str::<Plugin>(
+                r#"<plugin xmlns="http://maven.apache.org/POM/4.0.0" xmlns:v="urn:v"><v:extra>1</v:extra><artifactId>a</artifactId><goals><goal>b</goal></goals></plugin>"#,
+            )
+        });
+        let plugin = plugin.unwrap();
+        assert_eq!(unknown, ["plugin/v:extra", "plugin/goals"]);
+
+        let xml = to_string_with_config(&plugin, &CONFIG).unwrap();
+        assert_eq!(
+            xml,
+            r#"<Plugin xmlns="http://maven.apache.org/POM/4.0.0"><artifactId>a</artifactId><v:extra xmlns:v="urn:v">1</v:extra><goals><goal>b</goal></goals></Plugin>"#
+        );
+    }
 }
@@ -1,13 +1,305 @@
-mod maven;
-mod sort_pom;
+mod convert;
+pub(crate) mod document;
+mod effective_pom;
+mod lint_pom;
+pub(crate) mod maven;
+mod namespace;
+mod set_version;
+pub(crate) mod settings;
+pub(crate) mod sort_pom;
+pub(crate) mod sort_settings;
+pub(crate) mod toolchains;
+
+use crate::batch;
+use crate::diagnostic::Diagnostic;
+use anyhow::{Error, Result};
+use document::Document;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use xml::common::{Position, TextPosition};
+use xml::reader::{EventReader, ParserConfig, ParserConfig2, XmlEvent};
+use xml::{EmitterConfig, Encoding};
+use yaserde::de::Deserializer;
+use yaserde::YaDeserialize;
 
 #[derive(Debug, clap::Subcommand)]
 pub enum Args {
+    EffectivePom(effective_pom::Args),
+    JsonToPom(convert::Args),
+    LintPom(lint_pom::Args),
+    PomToJson(convert::Args),
+    PomToYaml(convert::Args),
+    SetVersion(set_version::Args),
     SortPom(sort_pom::Args),
+    SortSettings(sort_settings::Args),
+    SortToolchains(sort_settings::Args),
+    YamlToPom(convert::Args),
 }
 
-pub fn main(args: Args) -> anyhow::Result<()> {
+pub fn main(args: Args) -> Result<()> {
     match args {
+        Args::EffectivePom(args) => effective_pom::main(args),
+        Args::JsonToPom(args) => convert::json_to_pom(args),
+        Args::LintPom(args) => lint_pom::main(args),
+        Args::PomToJson(args) => convert::pom_to_json(args),
+        Args::PomToYaml(args) => convert::pom_to_yaml(args),
+        Args::SetVersion(args) => set_version::main(args),
         Args::SortPom(args) => sort_pom::main(args),
+        Args::SortSettings(args) => sort_settings::settings(args),
+        Args::SortToolchains(args) => sort_settings::toolchains(args),
+        Args::YamlToPom(args) => convert::yaml_to_pom(args),
+    }
+}
+
+fn map_events<R, F>(reader: R, mut f: F) -> Result<Vec<u8>>
+where
+    R: Read,
+    F: FnMut(XmlEvent) -> XmlEvent,
+{
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(Vec::new());
+
+    let config = parser_config(ParserConfig::new().ignore_root_level_whitespace(false));
+    for event in EventReader::new_with_config(reader, config) {
+        let event = event?;
+        if let XmlEvent::StartDocument { .. } = event {
+            continue;
+        }
+
+        if let Some(event) = f(event).as_writer_event() {
+            writer.write(event)?;
+        }
+    }
+
+    Ok(writer.into_inner())
+}
+
+fn warn_unknown(path: &Path, unknown: &[String]) {
+    batch::report_warnings(path, &unknown_warnings(unknown));
+}
+
+fn unknown_warnings(unknown: &[String]) -> Vec<String> {
+    if unknown.is_empty() {
+        return Vec::new();
+    }
+    vec![format!(
+        "kept unrecognized elements as-is: {}",
+        unknown.join(", ")
+    )]
+}
+
+fn parser_config(config: ParserConfig) -> ParserConfig2 {
+    config
+        .override_encoding(Some(Encoding::Utf8))
+        .ignore_invalid_encoding_declarations(true)
+}
+
+fn read<T: YaDeserialize>(path: &Path) -> Result<(Document, T, Vec<String>)> {
+    let (document, text) = Document::decode(&fs::read(path)?)?;
+    let (value, unknown) = from_str(path, &text, &text)?;
+    Ok((document, value, unknown))
+}
+
+// Also returns the paths of the elements that were kept without being recognized.
+fn from_str<T: YaDeserialize>(path: &Path, text: &str, input: &str) -> Result<(T, Vec<String>)> {
+    let consumed = Cell::new(0);
+    let reader = Counting {
+        inner: input.as_bytes(),
+        count: &consumed,
+    };
+    let config = parser_config(
+        ParserConfig::new()
+            .trim_whitespace(true)
+            .whitespace_to_characters(true)
+            .cdata_to_characters(true)
+            .ignore_comments(true)
+            .coalesce_characters(true),
+    );
+
+    let mut deserializer = Deserializer::new(EventReader::new_with_config(reader, config));
+    let (value, unknown) = maven::collect_unknown(|| T::deserialize(&mut deserializer));
+    let value = value.map_err(|message| {
+        let (offset, element) = element_at(input, consumed.get());
+        let offset = if input == text {
+            offset
+        } else {
+            find_element(text, &element).unwrap_or(0)
+        };
+
+        let context = element
+            .iter()
+            .map(|(name, n)| match n {
+                1 => name.clone(),
+                n => format!("{name}[{n}]"),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        Error::from(Diagnostic::at(path, text, offset, &message).context(context))
+    })?;
+    Ok((value, unknown))
+}
+
+fn syntax(path: &Path, text: &str, error: Error) -> Error {
+    match error.downcast_ref::<xml::reader::Error>() {
+        Some(e) => {
+            let position = e.position();
+            let (line, column) = (position.row as usize + 1, position.column as usize + 1);
+            Diagnostic::new(path, text, line, column, e.msg()).into()
+        }
+        None => error,
+    }
+}
+
+struct Counting<'a, R> {
+    inner: R,
+    count: &'a Cell<usize>,
+}
+
+impl<R: Read> Read for Counting<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.set(self.count.get() + n);
+        Ok(n)
+    }
+}
+
+struct Lines<'a> {
+    text: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        let starts = [0]
+            .into_iter()
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { text, starts }
+    }
+
+    fn offset(&self, position: TextPosition) -> usize {
+        let Some(&start) = self.starts.get(position.row as usize) else {
+            return self.text.len();
+        };
+        self.text[start..]
+            .char_indices()
+            .nth(position.column as usize)
+            .map_or(self.text.len(), |(i, _)| start + i)
+    }
+}
+
+fn walk<F>(text: &str, mut f: F)
+where
+    F: FnMut(usize, bool, &[(String, usize)]) -> bool,
+{
+    let lines = Lines::new(text);
+    let mut stack = Vec::new();
+    let mut counts = vec![HashMap::<String, usize>::new()];
+
+    let config = parser_config(ParserConfig::new());
+    let mut reader = EventReader::new_with_config(text.as_bytes(), config);
+    loop {
+        let event = match reader.next() {
+            Ok(XmlEvent::EndDocument) => break,
+            Ok(event) => event,
+            Err(e) => {
+                f(lines.offset(e.position()), false, &stack);
+                break;
+            }
+        };
+        let offset = lines.offset(reader.position());
+
+        let start = if let XmlEvent::StartElement { name, .. } = &event {
+            let count = counts.last_mut().unwrap();
+            let count = count.entry(name.local_name.clone()).or_default();
+            *count += 1;
+            stack.push((name.local_name.clone(), *count));
+            counts.push(HashMap::new());
+            true
+        } else {
+            false
+        };
+
+        if !f(offset, start, &stack) {
+            break;
+        }
+
+        if let XmlEvent::EndElement { .. } = event {
+            stack.pop();
+            counts.pop();
+        }
+    }
+}
+
+fn element_at(text: &str, consumed: usize) -> (usize, Vec<(String, usize)>) {
+    let mut events = Vec::new();
+    walk(text, |offset, _, stack| {
+        events.push((offset, stack.to_vec()));
+        offset < consumed
+    });
+
+    // The parser reads a little past the last event it hands out, so an event only counts as
+    // consumed once the one after it has started.
+    let last = (1..events.len())
+        .rev()
+        .find(|&i| events[i].0 <= consumed)
+        .map_or(0, |i| i - 1);
+    events.into_iter().nth(last).unwrap_or_default()
+}
+
+fn find_element(text: &str, element: &[(String, usize)]) -> Option<usize> {
+    let mut found = None;
+    walk(text, |offset, start, stack| {
+        if start && stack == element {
+            found = Some(offset);
+        }
+        found.is_none()
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let text = r#"<?xml version="1.0" encoding="ISO-8859-1"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <build>
+    <plugins>
+      <plugin/>
+      <plugin>
+        <configuration>oops</configuration>
+      </plugin>
+    </plugins>
+  </build>
+</project>
+"#;
+        let Err(error) = from_str::<maven::Model>(Path::new("pom.xml"), text, text) else {
+            panic!();
+        };
+        assert_eq!(
+            error.to_string(),
+            r#"pom.xml:7:24: unexpected Characters(oops)
+  in project/build/plugins/plugin[2]/configuration
+  |
+7 |         <configuration>oops</configuration>
+  |                        ^"#
+        );
+    }
+
+    #[test]
+    fn test_syntax() {
+        let text = "<project>\n  <a></b>\n</project>\n";
+        let Err(error) = from_str::<maven::Model>(Path::new("pom.xml"), text, text) else {
+            panic!();
+        };
+        assert!(error.to_string().starts_with("pom.xml:2:"), "{error}");
+        assert!(error.to_string().contains("\n  in project/a\n"), "{error}");
     }
 }
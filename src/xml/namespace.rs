@@ -0,0 +1,132 @@
+use anyhow::Result;
+use std::io::Read;
+use xml::namespace::Namespace;
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+
+// The namespace of the root element and the prefixes declared on it.
+pub fn of<R: Read>(reader: R) -> Result<(Option<String>, Namespace)> {
+    let config = super::parser_config(ParserConfig::new());
+    for event in EventReader::new_with_config(reader, config) {
+        if let XmlEvent::StartElement {
+            name, namespace, ..
+        } = event?
+        {
+            return Ok((name.namespace, namespace));
+        }
+    }
+
+    Ok((None, Namespace::empty()))
+}
+
+pub fn rebind<R: Read>(reader: R, from: &str, to: &str) -> Result<Vec<u8>> {
+    super::map_events(reader, |event| match event {
+        XmlEvent::StartElement {
+            mut name,
+            attributes,
+            mut namespace,
+        } => {
+            if name.namespace.as_deref() == Some(from) {
+                name.namespace = Some(to.to_string());
+            }
+            for uri in namespace.0.values_mut() {
+                if uri == from {
+                    *uri = to.to_string();
+                }
+            }
+
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            }
+        }
+        XmlEvent::EndElement { mut name } => {
+            if name.namespace.as_deref() == Some(from) {
+                name.namespace = Some(to.to_string());
+            }
+
+            XmlEvent::EndElement { name }
+        }
+        event => event,
+    })
+}
+
+// Undoes `rebind` on serialized output. `from` goes back to `to`, or goes away when the input had no
+// namespace, and prefixes the serializer declares on its own are dropped unless `declared` has them.
+pub fn restore<R: Read>(
+    reader: R,
+    from: &str,
+    to: Option<&str>,
+    declared: &Namespace,
+) -> Result<Vec<u8>> {
+    let rename = |name: &mut xml::name::OwnedName| {
+        if name.namespace.as_deref() == Some(from) {
+            name.namespace = to.map(str::to_string);
+        }
+    };
+
+    super::map_events(reader, |event| match event {
+        XmlEvent::StartElement {
+            mut name,
+            attributes,
+            mut namespace,
+        } => {
+            rename(&mut name);
+            let used = attributes
+                .iter()
+                .filter_map(|a| a.name.prefix.clone())
+                .chain(name.prefix.clone())
+                .collect::<Vec<_>>();
+            namespace.0.retain(|prefix, uri| match (uri == from, to) {
+                (true, Some(to)) => {
+                    *uri = to.to_string();
+                    true
+                }
+                (true, None) => false,
+                (false, _) => {
+                    matches!(prefix.as_str(), "xml" | "xmlns")
+                        || declared.get(prefix).is_some()
+                        || used.contains(prefix)
+                }
+            });
+
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            }
+        }
+        XmlEvent::EndElement { mut name } => {
+            rename(&mut name);
+            XmlEvent::EndElement { name }
+        }
+        event => event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_of() {
+        let (ns, declared) = of(r#"<settings xmlns="urn:a"><x/></settings>"#.as_bytes()).unwrap();
+        assert_eq!(ns, Some("urn:a".to_string()));
+        assert_eq!(declared.get(""), Some("urn:a"));
+        assert_eq!(of("<settings/>".as_bytes()).unwrap().0, None);
+    }
+
+    #[test]
+    fn test_rebind() {
+        let xml = rebind(
+            r#"<a xmlns="urn:a" xmlns:b="urn:b"><b:c>1</b:c><d>2</d></a>"#.as_bytes(),
+            "urn:a",
+            "urn:z",
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(xml).unwrap(),
+            r#"<a xmlns="urn:z" xmlns:b="urn:b"><b:c>1</b:c><d>2</d></a>"#
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use super::document::Document;
+use crate::batch;
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+use xml::common::Position;
+use xml::escape::escape_str_pcdata;
+use xml::reader::{EventReader, ParserConfig, XmlEvent};
+
+pub const PATTERNS: &[&str] = &["pom.xml"];
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    coordinates: String,
+    version: String,
+
+    #[arg(default_value = ".")]
+    paths: Vec<PathBuf>,
+
+    #[command(flatten)]
+    selection: batch::Selection,
+}
+
+pub fn main(args: Args) -> Result<()> {
+    let (group_id, artifact_id) = args
+        .coordinates
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected groupId:artifactId, found `{}`", args.coordinates))?;
+
+    let mut poms = Vec::new();
+    let mut scans = Vec::new();
+    for path in batch::expand(&args.paths, &args.selection, PATTERNS)? {
+        let (document, text) = Document::decode(&fs::read(&path)?)
+            .map_err(|e| e.context(format!("failed to read {}", path.display())))?;
+        let scan = scan(&text, group_id, artifact_id)
+            .map_err(|e| e.context(format!("failed to read {}", path.display())))?;
+        let warnings = scan
+            .skipped
+            .iter()
+            .map(|version| format!("skipped `{version}`, which only partly uses a property"))
+            .collect::<Vec<_>>();
+        batch::report_warnings(&path, &warnings);
+        scans.push((fs::canonicalize(&path)?, scan));
+        poms.push((path, document, text));
+    }
+
+    let edits = edits(&scans);
+    for ((path, document, text), edits) in poms.into_iter().zip(edits) {
+        if edits.is_empty() {
+            continue;
+        }
+
+        // The text still starts with the original declaration, so only the encoding is reapplied.
+        let document = Document {
+            declaration: None,
+            ..document
+        };
+        let output = apply(&text, edits.clone(), &args.version);
+        fs::write(&path, document.encode(&output))?;
+        println!("{}: {} updated", path.display(), edits.len());
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+struct Scan {
+    versions: Vec<Range<usize>>,
+    references: Vec<String>,
+    // Versions such as `${x}-SNAPSHOT`, which neither the literal nor the property can update alone.
+    skipped: Vec<String>,
+    properties: HashMap<String, Vec<Range<usize>>>,
+}
+
+#[derive(Default)]
+struct Artifact {
+    group_id: Option<String>,
+    artifact_id: Option<String>,
+    version: Option<(String, Range<usize>)>,
+}
+
+fn scan(text: &str, group_id: &str, artifact_id: &str) -> Result<Scan> {
+    let lines = text
+        .match_indices('\n')
+        .map(|(i, _)| i + 1)
+        .fold(vec![0], |mut v, i| {
+            v.push(i);
+            v
+        });
+    let offset = |row: u64, column: u64| {
+        let start = lines[row as usize];
+        text[start..]
+            .char_indices()
+            .nth(column as usize)
+            .map_or(text.len(), |(i, _)| start + i)
+    };
+
+    let mut scan = Scan::default();
+    let mut path = Vec::<String>::new();
+    let mut artifacts = Vec::<Option<Artifact>>::new();
+
+    let config = super::parser_config(ParserConfig::new());
+    let mut reader = EventReader::new_with_config(text.as_bytes(), config);
+    loop {
+        let event = reader.next()?;
+        let position = reader.position();
+        match event {
+            XmlEvent::StartElement { name, .. } => {
+                let parent = path.last().map(String::as_str);
+                let artifact = matches!(
+                    (parent, name.local_name.as_str()),
+                    (Some("dependencies"), "dependency") | (Some("plugins"), "plugin")
+                );
+                artifacts.push(artifact.then(Artifact::default));
+                path.push(name.local_name);
+            }
+            XmlEvent::Characters(s) => {
+                let start = offset(position.row, position.column);
+                let end = text[start..].find('<').map_or(text.len(), |i| start + i);
+                let raw = &text[start..end];
+                let range = (start + raw.len() - raw.trim_start().len())
+                    ..(end - (raw.len() - raw.trim_end().len()));
+                let value = s.trim().to_string();
+
+                let depth = path.len();
+                let element = path.last().map(String::as_str).unwrap_or_default();
+                if let Some(Some(artifact)) = artifacts.get_mut(depth.wrapping_sub(2)) {
+                    match element {
+                        "groupId" => artifact.group_id = Some(value),
+                        "artifactId" => artifact.artifact_id = Some(value),
+                        "version" => artifact.version = Some((value, range)),
+                        _ => {}
+                    }
+                } else if depth >= 2 && path[depth - 2] == "properties" {
+                    scan.properties
+                        .entry(element.to_string())
+                        .or_default()
+                        .push(range);
+                }
+            }
+            XmlEvent::EndElement { name } => {
+                path.pop();
+                let Some(Some(artifact)) = artifacts.pop() else {
+                    continue;
+                };
+
+                let default_group_id =
+                    (name.local_name == "plugin").then_some("org.apache.maven.plugins");
+                if artifact.group_id.as_deref().or(default_group_id) != Some(group_id)
+                    || artifact.artifact_id.as_deref() != Some(artifact_id)
+                {
+                    continue;
+                }
+
+                if let Some((version, range)) = artifact.version {
+                    match version.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+                        Some(property) if !property.contains('}') => {
+                            scan.references.push(property.to_string())
+                        }
+                        _ if version.contains("${") => scan.skipped.push(version),
+                        _ => scan.versions.push(range),
+                    }
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+
+    Ok(scan)
+}
+
+// A property is updated in the nearest POM that defines it, in the referencing POM's directory or
+// above it, the way a module inherits it from its parents.
+fn edits(scans: &[(PathBuf, Scan)]) -> Vec<Vec<Range<usize>>> {
+    let mut edits = scans
+        .iter()
+        .map(|(_, scan)| scan.versions.clone())
+        .collect::<Vec<_>>();
+    let mut updated = HashSet::new();
+    for (path, scan) in scans {
+        for name in &scan.references {
+            let owner = path.ancestors().skip(1).find_map(|dir| {
+                scans.iter().position(|(other, scan)| {
+                    other.parent() == Some(dir) && scan.properties.contains_key(name)
+                })
+            });
+            if let Some(i) = owner.filter(|&i| updated.insert((i, name))) {
+                edits[i].extend(scans[i].1.properties[name].iter().cloned());
+            }
+        }
+    }
+    edits
+}
+
+fn apply(text: &str, mut edits: Vec<Range<usize>>, version: &str) -> String {
+    edits.sort_by_key(|r| r.start);
+
+    let version = escape_str_pcdata(version);
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in edits {
+        result.push_str(&text[last..range.start]);
+        result.push_str(&version);
+        last = range.end;
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POM: &str = r#"<?xml version="1.0"?>
+<project>
+  <properties>
+    <guava.version>31.0</guava.version>
+  </properties>
+  <dependencies>
+    <!-- keep -->
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+      <version>${guava.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>org.example</groupId>
+      <version> 1.0 </version>
+      <artifactId>lib</artifactId>
+    </dependency>
+  </dependencies>
+  <build><plugins><plugin>
+    <artifactId>maven-jar-plugin</artifactId><version>3.0</version>
+  </plugin></plugins></build>
+</project>
+"#;
+
+    fn set(text: &str, group_id: &str, artifact_id: &str, version: &str) -> String {
+        let scan = scan(text, group_id, artifact_id).unwrap();
+        let mut edits = scan.versions;
+        for property in scan.references {
+            edits.extend(scan.properties[&property].iter().cloned());
+        }
+        apply(text, edits, version)
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(
+            set(POM, "org.example", "lib", "2.0"),
+            POM.replace("<version> 1.0 </version>", "<version> 2.0 </version>")
+        );
+        assert_eq!(
+            set(POM, "org.apache.maven.plugins", "maven-jar-plugin", "3.1"),
+            POM.replace("<version>3.0</version>", "<version>3.1</version>")
+        );
+    }
+
+    #[test]
+    fn test_property() {
+        assert_eq!(
+            set(POM, "com.google.guava", "guava", "33.0"),
+            POM.replace("31.0", "33.0")
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            set(POM, "org.example", "lib", "[1.0,2.0)&<3"),
+            POM.replace(
+                "<version> 1.0 </version>",
+                "<version> [1.0,2.0)&amp;&lt;3 </version>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_encoding() {
+        let pom = |version: &[u8]| {
+            let head =
+                b"<?xml version='1.0' encoding='ISO-8859-1'?>\n<project><name>Jos\xE9</name>";
+            let dependency =
+                b"<dependencies><dependency><groupId>g</groupId><artifactId>a</artifactId>";
+            let tail = b"</dependency></dependencies></project>\n";
+            [
+                &head[..],
+                dependency,
+                b"<version>",
+                version,
+                b"</version>",
+                tail,
+            ]
+            .concat()
+        };
+
+        let (document, text) = Document::decode(&pom(b"1")).unwrap();
+        let document = Document {
+            declaration: None,
+            ..document
+        };
+        assert_eq!(document.encode(&set(&text, "g", "a", "2")), pom(b"2"));
+    }
+
+    #[test]
+    fn test_unmatched() {
+        assert_eq!(set(POM, "org.example", "other", "9"), POM);
+    }
+
+    #[test]
+    fn test_partial_property() {
+        let pom = POM.replace("${guava.version}", "${guava.version}-jre");
+        let scan = scan(&pom, "com.google.guava", "guava").unwrap();
+        assert!(scan.versions.is_empty() && scan.references.is_empty());
+        assert_eq!(scan.skipped, ["${guava.version}-jre"]);
+    }
+
+    #[test]
+    fn test_reactor() {
+        let parent = "<project><properties><v>1</v></properties></project>";
+        let child = "<project><dependencies><dependency>\
+            <groupId>g</groupId><artifactId>a</artifactId><version>${v}</version>\
+            </dependency></dependencies></project>";
+        let scans = [
+            ("/a/pom.xml", parent),
+            ("/a/child/pom.xml", child),
+            ("/b/pom.xml", parent),
+        ]
+        .map(|(path, text)| (PathBuf::from(path), scan(text, "g", "a").unwrap()));
+        let edits = edits(&scans);
+        assert_eq!(edits[0].len(), 1);
+        assert!(edits[1].is_empty() && edits[2].is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+use super::maven::{Configuration, Profiles, Unknown};
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(
+    namespace = "http://maven.apache.org/POM/4.0.0",
+    namespace = "xsi: http://www.w3.org/2001/XMLSchema-instance",
+    rename = "settings"
+)]
+pub struct Settings {
+    #[yaserde(rename = "localRepository")]
+    pub local_repository: Option<String>,
+
+    #[yaserde(rename = "interactiveMode")]
+    pub interactive_mode: Option<bool>,
+
+    #[yaserde(rename = "usePluginRegistry")]
+    pub use_plugin_registry: Option<bool>,
+
+    pub offline: Option<bool>,
+    pub proxies: Option<Proxies>,
+    pub servers: Option<Servers>,
+    pub mirrors: Option<Mirrors>,
+    pub profiles: Option<Profiles>,
+
+    #[yaserde(rename = "activeProfiles")]
+    pub active_profiles: Option<ActiveProfiles>,
+
+    #[yaserde(rename = "pluginGroups")]
+    pub plugin_groups: Option<PluginGroups>,
+
+    #[yaserde(attribute, prefix = "xsi", rename = "schemaLocation")]
+    pub schema_location: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Proxies {
+    #[yaserde(rename = "proxy")]
+    pub proxies: Vec<Proxy>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Proxy {
+    pub active: Option<bool>,
+    pub protocol: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub port: Option<String>,
+    pub host: Option<String>,
+
+    #[yaserde(rename = "nonProxyHosts")]
+    pub non_proxy_hosts: Option<String>,
+
+    pub id: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Servers {
+    #[yaserde(rename = "server")]
+    pub servers: Vec<Server>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Server {
+    pub username: Option<String>,
+    pub password: Option<String>,
+
+    #[yaserde(rename = "privateKey")]
+    pub private_key: Option<String>,
+
+    pub passphrase: Option<String>,
+
+    #[yaserde(rename = "filePermissions")]
+    pub file_permissions: Option<String>,
+
+    #[yaserde(rename = "directoryPermissions")]
+    pub directory_permissions: Option<String>,
+
+    pub configuration: Option<Configuration>,
+    pub id: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Mirrors {
+    #[yaserde(rename = "mirror")]
+    pub mirrors: Vec<Mirror>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Mirror {
+    #[yaserde(rename = "mirrorOf")]
+    pub mirror_of: Option<String>,
+
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub layout: Option<String>,
+
+    #[yaserde(rename = "mirrorOfLayouts")]
+    pub mirror_of_layouts: Option<String>,
+
+    pub blocked: Option<bool>,
+    pub id: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct ActiveProfiles {
+    #[yaserde(rename = "activeProfile")]
+    pub active_profiles: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct PluginGroups {
+    #[yaserde(rename = "pluginGroup")]
+    pub plugin_groups: Vec<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
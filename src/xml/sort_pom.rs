@@ -1,29 +1,180 @@
+use super::document::Document;
+use super::maven::Model;
+use crate::batch::{self, Outcome};
 use anyhow::{Error, Result};
-use std::fs::File;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+pub const PATTERNS: &[&str] = &["pom.xml"];
 
 #[derive(Debug, clap::Args)]
 pub struct Args {
-    path: PathBuf,
+    #[arg(short, long)]
+    recursive: bool,
+
+    #[arg(short, long, requires = "recursive")]
+    keep_going: bool,
+
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[command(flatten)]
+    options: batch::Options,
 }
 
 pub fn main(args: Args) -> Result<()> {
-    let f = File::open(&args.path)?;
-    let pom: super::maven::Model = yaserde::de::from_reader(f).map_err(Error::msg)?;
+    if !args.recursive {
+        return batch::run(&args.paths, &args.options, PATTERNS, |path, input| {
+            let (output, warnings) = sort(path, input)?;
+            batch::report_warnings(path, &warnings);
+            Ok(output)
+        });
+    }
+
+    let paths = batch::expand(&args.paths, &args.options.selection, PATTERNS)?;
+    let outcomes = recurse(paths, &args.options, args.keep_going);
+    batch::report(outcomes, args.options.check)
+}
+
+// Sorts each POM as it is found so that it is parsed only once, then queues up its modules.
+fn recurse(
+    paths: Vec<PathBuf>,
+    options: &batch::Options,
+    keep_going: bool,
+) -> Vec<(PathBuf, Outcome)> {
+    let mut queue = VecDeque::from(paths);
+    let mut visited = HashSet::new();
+    let mut outcomes = Vec::new();
+
+    while let Some(path) = queue.pop_front() {
+        if !visited.insert(fs::canonicalize(&path).unwrap_or_else(|_| path.clone())) {
+            continue;
+        }
+
+        let outcome = batch::process(&path, options, |path, input| {
+            let (document, text) = Document::decode(input)?;
+            let (pom, unknown): (Model, _) = super::from_str(path, &text, &text)?;
+            super::warn_unknown(path, &unknown);
+            queue.extend(modules(path, &pom));
+
+            let mut output = Vec::new();
+            write(&pom, &document, &mut output)?;
+            Ok(output)
+        });
+        let failed = matches!(outcome, Outcome::Failed(_));
+        outcomes.push((path, outcome));
+        if failed && !keep_going {
+            break;
+        }
+    }
+    outcomes
+}
+
+pub fn sort(path: &Path, input: &[u8]) -> Result<(Vec<u8>, Vec<String>)> {
+    let (document, text) = Document::decode(input)?;
+    sort_text(path, &document, &text)
+}
+
+// Returns the sorted document along with warnings about it.
+pub fn sort_text(path: &Path, document: &Document, text: &str) -> Result<(Vec<u8>, Vec<String>)> {
+    let (pom, unknown): (Model, _) = super::from_str(path, text, text)?;
+
+    let mut output = Vec::new();
+    write(&pom, document, &mut output)?;
+    Ok((output, super::unknown_warnings(&unknown)))
+}
 
-    let mut f = File::create(&args.path)?;
-    yaserde::ser::serialize_with_writer(
-        &pom,
-        &mut f,
+pub fn write<W: Write>(pom: &Model, document: &Document, mut writer: W) -> Result<()> {
+    let body = yaserde::ser::to_string_with_config(
+        pom,
         &yaserde::ser::Config {
             perform_indent: true,
-            write_document_declaration: true,
+            write_document_declaration: false,
             indent_string: None,
         },
     )
     .map_err(Error::msg)?;
-    f.write_all(b"\n")?;
+    writer.write_all(&document.encode(&format!("{body}\n")))?;
 
     Ok(())
 }
+
+fn modules(path: &Path, pom: &Model) -> Vec<PathBuf> {
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+
+    let profiles = pom.profiles.iter().flat_map(|p| &p.profiles);
+    pom.modules
+        .iter()
+        .chain(profiles.filter_map(|p| p.modules.as_ref()))
+        .flat_map(|m| &m.modules)
+        .map(|module| {
+            let path = base.join(module.trim());
+            if path.is_dir() {
+                path.join("pom.xml")
+            } else {
+                path
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recurse() {
+        let dir = std::env::temp_dir().join(format!("junk-reactor-{}", std::process::id()));
+        let pom = |modules: &str, profile: &str| {
+            format!(
+                "<project><modules>{modules}</modules><profiles><profile><id>p</id>\
+                 <modules>{profile}</modules></profile></profiles></project>"
+            )
+        };
+        for (name, text) in [
+            (
+                "",
+                pom(
+                    "<module>broken</module><module>a</module>",
+                    "<module>b</module>",
+                ),
+            ),
+            // A cycle back to the root, which must not be visited twice.
+            ("a", pom("<module>..</module>", "")),
+            ("b", pom("", "")),
+            ("broken", "<project><modules>".to_string()),
+        ] {
+            fs::create_dir_all(dir.join(name)).unwrap();
+            fs::write(dir.join(name).join("pom.xml"), text).unwrap();
+        }
+
+        let options = batch::Options {
+            check: true,
+            ..batch::Options::default()
+        };
+        let summary = |keep_going| {
+            recurse(vec![dir.join("pom.xml")], &options, keep_going)
+                .into_iter()
+                .map(|(path, outcome)| {
+                    let name = path.strip_prefix(&dir).unwrap().display().to_string();
+                    (name, matches!(outcome, Outcome::Failed(_)))
+                })
+                .collect::<Vec<_>>()
+        };
+        let outcomes = summary(true);
+        assert_eq!(
+            outcomes,
+            [
+                ("pom.xml".to_string(), false),
+                ("broken/pom.xml".to_string(), true),
+                ("a/pom.xml".to_string(), false),
+                ("b/pom.xml".to_string(), false),
+            ]
+        );
+        assert_eq!(summary(false), outcomes[..2]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
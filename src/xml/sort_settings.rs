@@ -0,0 +1,137 @@
+use super::{document::Document, maven, namespace, settings::Settings, toolchains::Toolchains};
+use crate::batch;
+use anyhow::{Error, Result};
+use std::path::{Path, PathBuf};
+use yaserde::{YaDeserialize, YaSerialize};
+
+pub const SETTINGS: &[&str] = &["settings.xml"];
+pub const TOOLCHAINS: &[&str] = &["toolchains.xml"];
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[arg(required_unless_present_any = ["since", "staged"])]
+    paths: Vec<PathBuf>,
+
+    #[command(flatten)]
+    options: batch::Options,
+}
+
+pub fn settings(args: Args) -> Result<()> {
+    run::<Settings>(&args, SETTINGS)
+}
+
+pub fn toolchains(args: Args) -> Result<()> {
+    run::<Toolchains>(&args, TOOLCHAINS)
+}
+
+fn run<T: YaDeserialize + YaSerialize>(args: &Args, patterns: &[&str]) -> Result<()> {
+    batch::run(&args.paths, &args.options, patterns, |path, input| {
+        let (output, warnings) = sort::<T>(path, input)?;
+        batch::report_warnings(path, &warnings);
+        Ok(output)
+    })
+}
+
+pub fn sort<T: YaDeserialize + YaSerialize>(
+    path: &Path,
+    input: &[u8],
+) -> Result<(Vec<u8>, Vec<String>)> {
+    let (document, text) = Document::decode(input)?;
+    sort_text::<T>(path, &document, &text)
+}
+
+// The models reuse the POM types, which only accept elements in the POM namespace, so the file's own
+// namespace stands in for it while the file is read and is put back afterwards.
+pub fn sort_text<T: YaDeserialize + YaSerialize>(
+    path: &Path,
+    document: &Document,
+    text: &str,
+) -> Result<(Vec<u8>, Vec<String>)> {
+    let syntax = |e| super::syntax(path, text, e);
+    let (ns, declared) = namespace::of(text.as_bytes()).map_err(syntax)?;
+    let input = match &ns {
+        Some(ns) => namespace::rebind(text.as_bytes(), ns, maven::NAMESPACE).map_err(syntax)?,
+        None => text.as_bytes().to_vec(),
+    };
+    let (value, unknown): (T, _) = super::from_str(path, text, &String::from_utf8(input)?)?;
+
+    let output = yaserde::ser::serialize_with_writer(
+        &value,
+        Vec::new(),
+        &yaserde::ser::Config {
+            perform_indent: true,
+            write_document_declaration: false,
+            indent_string: None,
+        },
+    )
+    .map_err(Error::msg)?;
+    let output = namespace::restore(&output[..], maven::NAMESPACE, ns.as_deref(), &declared)?;
+    let mut output = String::from_utf8(output)?;
+    output.push('\n');
+    Ok((document.encode(&output), super::unknown_warnings(&unknown)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<settings xmlns="http://maven.apache.org/SETTINGS/1.2.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://maven.apache.org/SETTINGS/1.2.0 https://maven.apache.org/xsd/settings-1.2.0.xsd">
+  <servers>
+    <server>
+      <password>secret</password>
+      <id>central</id>
+    </server>
+  </servers>
+  <localRepository>/tmp/m2</localRepository>
+</settings>
+"#;
+        let (output, _) = sort::<Settings>(Path::new("settings.xml"), input.as_bytes()).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<settings xmlns="http://maven.apache.org/SETTINGS/1.2.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://maven.apache.org/SETTINGS/1.2.0 https://maven.apache.org/xsd/settings-1.2.0.xsd">
+  <localRepository>/tmp/m2</localRepository>
+  <servers>
+    <server>
+      <password>secret</password>
+      <id>central</id>
+    </server>
+  </servers>
+</settings>
+"#
+        );
+    }
+
+    #[test]
+    fn test_settings_without_namespace() {
+        let input = r#"<settings>
+  <mirrors>
+    <mirror>
+      <url>https://example.com/maven</url>
+      <mirrorOf>*</mirrorOf>
+      <id>example</id>
+    </mirror>
+  </mirrors>
+  <offline>true</offline>
+</settings>
+"#;
+        let (output, _) = sort::<Settings>(Path::new("settings.xml"), input.as_bytes()).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            r#"<settings>
+  <offline>true</offline>
+  <mirrors>
+    <mirror>
+      <mirrorOf>*</mirrorOf>
+      <url>https://example.com/maven</url>
+      <id>example</id>
+    </mirror>
+  </mirrors>
+</settings>
+"#
+        );
+    }
+}
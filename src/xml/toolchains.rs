@@ -0,0 +1,61 @@
+use super::maven::{Configuration, Properties, Unknown};
+use yaserde_derive::{YaDeserialize, YaSerialize};
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(
+    namespace = "http://maven.apache.org/POM/4.0.0",
+    namespace = "xsi: http://www.w3.org/2001/XMLSchema-instance",
+    rename = "toolchains"
+)]
+pub struct Toolchains {
+    #[yaserde(rename = "toolchain")]
+    pub toolchains: Vec<Toolchain>,
+
+    #[yaserde(attribute, prefix = "xsi", rename = "schemaLocation")]
+    pub schema_location: Option<String>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[derive(YaDeserialize, YaSerialize)]
+#[yaserde(namespace = "http://maven.apache.org/POM/4.0.0")]
+pub struct Toolchain {
+    #[yaserde(rename = "type")]
+    pub toolchain_type: Option<String>,
+
+    pub provides: Option<Properties>,
+    pub configuration: Option<Configuration>,
+
+    #[yaserde(flatten, rename = "__unknown")]
+    pub unknown: Unknown,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaserde::de::from_str;
+    use yaserde::ser::{to_string_with_config, Config};
+
+    #[test]
+    fn test_provides() {
+        let toolchains: Toolchains = from_str(
+            r#"<toolchains xmlns="http://maven.apache.org/POM/4.0.0"><toolchain><provides><version>17</version></provides><type>jdk</type></toolchain></toolchains>"#,
+        )
+        .unwrap();
+
+        let xml = to_string_with_config(
+            &toolchains,
+            &Config {
+                perform_indent: false,
+                write_document_declaration: false,
+                indent_string: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            xml,
+            r#"<toolchains xmlns="http://maven.apache.org/POM/4.0.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"><toolchain><type>jdk</type><provides><version>17</version></provides></toolchain></toolchains>"#
+        );
+    }
+}